@@ -0,0 +1,85 @@
+use crate::connector::admin::MeowithAdminConnector;
+use crate::dto::request::{CreateApplicationRequest, RenameEntityRequest};
+use crate::dto::response::{AppDto, AppList};
+use crate::error::{ConnectorError, ConnectorResponse};
+use uuid::Uuid;
+
+impl MeowithAdminConnector {
+    pub async fn create_app(&self, name: &str) -> ConnectorResponse<AppDto> {
+        let req = CreateApplicationRequest {
+            name: name.to_string(),
+        };
+        let response = self
+            .send(
+                "create_app",
+                self.client
+                    .post(format!("{}/api/app/create", self.dashboard_addr))
+                    .json(&req),
+            )
+            .await?;
+
+        response
+            .json::<AppDto>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("create_app", None))
+    }
+
+    /// Lists the apps owned by the token's user.
+    pub async fn list_apps(&self) -> ConnectorResponse<AppList> {
+        let response = self
+            .send(
+                "list_apps",
+                self.client
+                    .get(format!("{}/api/app/owned", self.dashboard_addr)),
+            )
+            .await?;
+
+        response
+            .json::<AppList>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("list_apps", None))
+    }
+
+    pub async fn fetch_app(&self, app_id: Uuid) -> ConnectorResponse<AppDto> {
+        let response = self
+            .send(
+                "fetch_app",
+                self.client
+                    .get(format!("{}/api/app/info/{}", self.dashboard_addr, app_id)),
+            )
+            .await?;
+
+        response
+            .json::<AppDto>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("fetch_app", None))
+    }
+
+    pub async fn rename_app(&self, app_id: Uuid, to: &str) -> ConnectorResponse<AppDto> {
+        let req = RenameEntityRequest { to: to.to_string() };
+        let response = self
+            .send(
+                "rename_app",
+                self.client
+                    .post(format!("{}/api/app/rename/{}", self.dashboard_addr, app_id))
+                    .json(&req),
+            )
+            .await?;
+
+        response
+            .json::<AppDto>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("rename_app", None))
+    }
+
+    /// Deletes the app along with all of its buckets.
+    pub async fn delete_app(&self, app_id: Uuid) -> ConnectorResponse<()> {
+        self.send(
+            "delete_app",
+            self.client
+                .delete(format!("{}/api/app/delete/{}", self.dashboard_addr, app_id)),
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,87 @@
+use crate::connector::admin::MeowithAdminConnector;
+use crate::dto::request::{CreateBucketRequest, EditBucketQuotaRequest};
+use crate::dto::response::{BucketDto, BucketList};
+use crate::error::{ConnectorError, ConnectorResponse};
+use uuid::Uuid;
+
+impl MeowithAdminConnector {
+    /// Creates a bucket, `encrypted` and `atomic_upload` can not be changed afterwards.
+    pub async fn create_bucket(
+        &self,
+        app_id: Uuid,
+        req: &CreateBucketRequest,
+    ) -> ConnectorResponse<BucketDto> {
+        let response = self
+            .send(
+                "create_bucket",
+                self.client
+                    .post(format!(
+                        "{}/api/bucket/create/{}",
+                        self.dashboard_addr, app_id
+                    ))
+                    .json(req),
+            )
+            .await?;
+
+        response
+            .json::<BucketDto>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("create_bucket", None))
+    }
+
+    pub async fn list_buckets(&self, app_id: Uuid) -> ConnectorResponse<BucketList> {
+        let response = self
+            .send(
+                "list_buckets",
+                self.client.get(format!(
+                    "{}/api/bucket/list/{}",
+                    self.dashboard_addr, app_id
+                )),
+            )
+            .await?;
+
+        response
+            .json::<BucketList>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("list_buckets", None))
+    }
+
+    /// Sets the bucket quota in bytes.
+    pub async fn set_bucket_quota(
+        &self,
+        app_id: Uuid,
+        bucket_id: Uuid,
+        quota: i64,
+    ) -> ConnectorResponse<BucketDto> {
+        let req = EditBucketQuotaRequest { quota };
+        let response = self
+            .send(
+                "set_bucket_quota",
+                self.client
+                    .post(format!(
+                        "{}/api/bucket/quota/{}/{}",
+                        self.dashboard_addr, app_id, bucket_id
+                    ))
+                    .json(&req),
+            )
+            .await?;
+
+        response
+            .json::<BucketDto>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("set_bucket_quota", None))
+    }
+
+    /// Deletes the bucket. Fails with [`NodeClientError::NotEmpty`](crate::error::NodeClientError::NotEmpty) unless the bucket has no files.
+    pub async fn delete_bucket(&self, app_id: Uuid, bucket_id: Uuid) -> ConnectorResponse<()> {
+        self.send(
+            "delete_bucket",
+            self.client.delete(format!(
+                "{}/api/bucket/delete/{}/{}",
+                self.dashboard_addr, app_id, bucket_id
+            )),
+        )
+        .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::connector::admin::MeowithAdminConnector;
+use crate::dto::request::{AddMemberRequest, MemberIdRequest, MemberRoleRequest};
+use crate::dto::response::MemberList;
+use crate::error::{ConnectorError, ConnectorResponse};
+use uuid::Uuid;
+
+impl MeowithAdminConnector {
+    /// Adds the user as a member of the app. The member starts without any roles.
+    pub async fn add_member(&self, app_id: Uuid, member_id: Uuid) -> ConnectorResponse<()> {
+        let req = AddMemberRequest { app_id, member_id };
+        self.send(
+            "add_member",
+            self.client
+                .post(format!("{}/api/app/member/add", self.dashboard_addr))
+                .json(&req),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Replaces the roles assigned to the member.
+    pub async fn set_member_roles(
+        &self,
+        member: &MemberIdRequest,
+        roles: Vec<String>,
+    ) -> ConnectorResponse<()> {
+        let req = MemberRoleRequest { roles };
+        self.send(
+            "set_member_roles",
+            self.client
+                .patch(format!(
+                    "{}/api/app/member/{}/{}",
+                    self.dashboard_addr, member.app_id, member.id
+                ))
+                .json(&req),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_member(&self, member: &MemberIdRequest) -> ConnectorResponse<()> {
+        self.send(
+            "remove_member",
+            self.client.delete(format!(
+                "{}/api/app/member/{}/{}",
+                self.dashboard_addr, member.app_id, member.id
+            )),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_members(&self, app_id: Uuid) -> ConnectorResponse<MemberList> {
+        let response = self
+            .send(
+                "list_members",
+                self.client
+                    .get(format!("{}/api/app/member/{}", self.dashboard_addr, app_id)),
+            )
+            .await?;
+
+        response
+            .json::<MemberList>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("list_members", None))
+    }
+}
@@ -0,0 +1,102 @@
+pub mod app;
+pub mod bucket;
+pub mod member;
+pub mod role;
+pub mod token;
+
+use crate::connector::builder::{bearer_header, ClientOptions};
+use crate::error::{ConnectorError, ConnectorResponse, RequestContext};
+use reqwest::header::{HeaderValue, AUTHORIZATION};
+use reqwest::{Client, RequestBuilder, Response};
+use std::time::Duration;
+
+/// Client for the dashboard api, used to manage applications and their resources.
+///
+/// Unlike [`MeowithConnector`](crate::connector::connector::MeowithConnector), it is not bound
+/// to a single app or bucket.
+#[derive(Clone)]
+pub struct MeowithAdminConnector {
+    client: Client,
+    auth: HeaderValue,
+    dashboard_addr: String,
+}
+
+impl MeowithAdminConnector {
+    /// # Panics
+    ///
+    /// When the token is not a valid header value or the http client fails to initialize.
+    /// Use [`MeowithAdminConnector::builder`] to handle these errors.
+    pub fn new(token: &str, dashboard_addr: String) -> Self {
+        Self::builder(token, dashboard_addr)
+            .build()
+            .expect("Failed to build the admin connector")
+    }
+
+    pub fn builder(token: &str, dashboard_addr: String) -> MeowithAdminConnectorBuilder {
+        MeowithAdminConnectorBuilder {
+            token: token.to_string(),
+            dashboard_addr,
+            client: ClientOptions::default(),
+        }
+    }
+
+    /// Sends the request, returning the response only if its status is successful.
+    async fn send(
+        &self,
+        operation: &'static str,
+        request: RequestBuilder,
+    ) -> ConnectorResponse<Response> {
+        let request = request.header(AUTHORIZATION, self.auth.clone()).build()?;
+        let context = RequestContext::new(operation, None).request(request.method(), request.url());
+        let response = match self.client.execute(request).await {
+            Ok(response) => response,
+            Err(err) => return Err(ConnectorError::from(err).with_context(context)),
+        };
+        if !response.status().is_success() {
+            return Err(ConnectorError::from_response(response)
+                .await
+                .with_context(context));
+        }
+        Ok(response)
+    }
+}
+
+/// Configures a [`MeowithAdminConnector`], reporting invalid configuration as an error instead of panicking.
+pub struct MeowithAdminConnectorBuilder {
+    token: String,
+    dashboard_addr: String,
+    client: ClientOptions,
+}
+
+impl MeowithAdminConnectorBuilder {
+    /// Uses an already configured client.
+    /// The timeout and user agent settings of this builder are then ignored.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client.client = Some(client);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.client.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for the whole request, including reading the response body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.client.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.client.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    pub fn build(self) -> ConnectorResponse<MeowithAdminConnector> {
+        Ok(MeowithAdminConnector {
+            client: self.client.build()?,
+            auth: bearer_header(&self.token)?,
+            dashboard_addr: self.dashboard_addr,
+        })
+    }
+}
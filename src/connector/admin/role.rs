@@ -0,0 +1,73 @@
+use crate::connector::admin::MeowithAdminConnector;
+use crate::dto::request::{AppRolePath, ModifyRoleRequest, ScopedPermission};
+use crate::dto::response::RoleList;
+use crate::error::{ConnectorError, ConnectorResponse};
+use uuid::Uuid;
+
+impl MeowithAdminConnector {
+    /// Creates an empty role, use [`Self::modify_role`] to grant it permissions.
+    pub async fn create_role(&self, role: &AppRolePath) -> ConnectorResponse<()> {
+        self.send(
+            "create_role",
+            self.client.post(format!(
+                "{}/api/role/{}/{}",
+                self.dashboard_addr,
+                role.app_id,
+                urlencoding::encode(&role.name)
+            )),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Replaces the per-bucket allowances of the role.
+    pub async fn modify_role(
+        &self,
+        role: &AppRolePath,
+        perms: Vec<ScopedPermission>,
+    ) -> ConnectorResponse<()> {
+        let req = ModifyRoleRequest { perms };
+        self.send(
+            "modify_role",
+            self.client
+                .patch(format!(
+                    "{}/api/role/{}/{}",
+                    self.dashboard_addr,
+                    role.app_id,
+                    urlencoding::encode(&role.name)
+                ))
+                .json(&req),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn delete_role(&self, role: &AppRolePath) -> ConnectorResponse<()> {
+        self.send(
+            "delete_role",
+            self.client.delete(format!(
+                "{}/api/role/{}/{}",
+                self.dashboard_addr,
+                role.app_id,
+                urlencoding::encode(&role.name)
+            )),
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_roles(&self, app_id: Uuid) -> ConnectorResponse<RoleList> {
+        let response = self
+            .send(
+                "list_roles",
+                self.client
+                    .get(format!("{}/api/role/{}", self.dashboard_addr, app_id)),
+            )
+            .await?;
+
+        response
+            .json::<RoleList>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("list_roles", None))
+    }
+}
@@ -0,0 +1,79 @@
+use crate::connector::admin::MeowithAdminConnector;
+use crate::dto::request::{
+    ScopedPermission, TokenDeleteRequest, TokenIssueRequest, TokenListRequest,
+};
+use crate::dto::response::{TokenIssueResponse, TokenListResponse};
+use crate::error::{ConnectorError, ConnectorResponse};
+use uuid::Uuid;
+
+impl MeowithAdminConnector {
+    /// Issues a new app token, scoped to the provided bucket permissions.
+    pub async fn issue_token(
+        &self,
+        app_id: Uuid,
+        name: &str,
+        perms: Vec<ScopedPermission>,
+    ) -> ConnectorResponse<TokenIssueResponse> {
+        let req = TokenIssueRequest {
+            app_id,
+            name: name.to_string(),
+            perms,
+        };
+        let response = self
+            .send(
+                "issue_token",
+                self.client
+                    .post(format!("{}/api/token/issue", self.dashboard_addr))
+                    .json(&req),
+            )
+            .await?;
+
+        response
+            .json::<TokenIssueResponse>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("issue_token", None))
+    }
+
+    /// Lists the app's tokens, optionally only the ones issued by `issuer`.
+    pub async fn list_tokens(
+        &self,
+        app_id: Uuid,
+        issuer: Option<Uuid>,
+    ) -> ConnectorResponse<TokenListResponse> {
+        let req = TokenListRequest { app_id, issuer };
+        let response = self
+            .send(
+                "list_tokens",
+                self.client
+                    .post(format!("{}/api/token/list", self.dashboard_addr))
+                    .json(&req),
+            )
+            .await?;
+
+        response
+            .json::<TokenListResponse>()
+            .await
+            .map_err(|err| ConnectorError::from(err).in_operation("list_tokens", None))
+    }
+
+    pub async fn delete_token(
+        &self,
+        app_id: Uuid,
+        issuer_id: Uuid,
+        name: &str,
+    ) -> ConnectorResponse<()> {
+        let req = TokenDeleteRequest {
+            app_id,
+            issuer_id,
+            name: name.to_string(),
+        };
+        self.send(
+            "delete_token",
+            self.client
+                .delete(format!("{}/api/token/delete", self.dashboard_addr))
+                .json(&req),
+        )
+        .await?;
+        Ok(())
+    }
+}
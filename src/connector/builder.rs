@@ -0,0 +1,167 @@
+use crate::connector::connector::MeowithConnector;
+use crate::connector::nodes::{NodePool, DEFAULT_NODE_COOLDOWN};
+use crate::connector::retry::RetryPolicy;
+use crate::connector::upload::DEFAULT_DURABLE_THRESHOLD;
+use crate::error::ConnectorResponse;
+use reqwest::header::HeaderValue;
+use reqwest::{Client, ClientBuilder};
+use std::time::Duration;
+use uuid::Uuid;
+
+/// Configures a [`MeowithConnector`], reporting invalid configuration as an error instead of panicking.
+pub struct MeowithConnectorBuilder {
+    token: String,
+    bucket_id: Uuid,
+    app_id: Uuid,
+    node_addrs: Vec<String>,
+    node_cooldown: Duration,
+    retry: RetryPolicy,
+    durable_threshold: u64,
+    client: ClientOptions,
+}
+
+impl MeowithConnectorBuilder {
+    pub fn new(token: &str, bucket_id: Uuid, app_id: Uuid, node_addr: String) -> Self {
+        Self {
+            token: token.to_string(),
+            bucket_id,
+            app_id,
+            node_addrs: vec![node_addr],
+            node_cooldown: DEFAULT_NODE_COOLDOWN,
+            retry: RetryPolicy::default(),
+            durable_threshold: DEFAULT_DURABLE_THRESHOLD,
+            client: ClientOptions::default(),
+        }
+    }
+
+    /// Adds a fallback node, used when the previously added ones are unreachable.
+    pub fn node(mut self, node_addr: String) -> Self {
+        self.node_addrs.push(node_addr);
+        self
+    }
+
+    pub fn nodes<I>(mut self, node_addrs: I) -> Self
+    where
+        I: IntoIterator<Item = String>,
+    {
+        self.node_addrs.extend(node_addrs);
+        self
+    }
+
+    /// How long a node is tried last after it failed, defaults to [`DEFAULT_NODE_COOLDOWN`].
+    pub fn node_cooldown(mut self, cooldown: Duration) -> Self {
+        self.node_cooldown = cooldown;
+        self
+    }
+
+    /// The retry policy applied to idempotent requests, defaults to [`RetryPolicy::default`].
+    pub fn retry_policy(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Size in bytes above which files are uploaded through a durable session,
+    /// defaults to [`DEFAULT_DURABLE_THRESHOLD`].
+    pub fn durable_threshold(mut self, threshold: u64) -> Self {
+        self.durable_threshold = threshold;
+        self
+    }
+
+    /// Uses an already configured client.
+    /// The timeout, user agent and pool settings of this builder are then ignored.
+    pub fn client(mut self, client: Client) -> Self {
+        self.client.client = Some(client);
+        self
+    }
+
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.client.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for each read of the response body.
+    pub fn read_timeout(mut self, timeout: Duration) -> Self {
+        self.client.read_timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout for the whole request, including reading the response body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.client.timeout = Some(timeout);
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.client.user_agent = Some(user_agent.to_string());
+        self
+    }
+
+    pub fn pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.client.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.client.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    pub fn build(self) -> ConnectorResponse<MeowithConnector> {
+        Ok(MeowithConnector::from_parts(
+            self.client.build()?,
+            bearer_header(&self.token)?,
+            self.bucket_id,
+            self.app_id,
+            NodePool::new(self.node_addrs, self.node_cooldown),
+            self.retry,
+            self.durable_threshold,
+        ))
+    }
+}
+
+/// The http client settings shared by the connector builders.
+#[derive(Default)]
+pub(crate) struct ClientOptions {
+    pub client: Option<Client>,
+    pub connect_timeout: Option<Duration>,
+    pub read_timeout: Option<Duration>,
+    pub timeout: Option<Duration>,
+    pub user_agent: Option<String>,
+    pub pool_idle_timeout: Option<Duration>,
+    pub pool_max_idle_per_host: Option<usize>,
+}
+
+impl ClientOptions {
+    pub fn build(self) -> ConnectorResponse<Client> {
+        if let Some(client) = self.client {
+            return Ok(client);
+        }
+        let mut builder = ClientBuilder::new();
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(user_agent) = self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// The `Authorization` header for the token, hidden from debug output.
+pub(crate) fn bearer_header(token: &str) -> ConnectorResponse<HeaderValue> {
+    let mut auth = HeaderValue::from_str(format!("Bearer {}", token).as_str())?;
+    auth.set_sensitive(true);
+    Ok(auth)
+}
@@ -1,5 +1,8 @@
-use crate::connector::headers::extract_filename;
-use crate::dto::range::{construct_pagination_query, DownloadRange, Range};
+use crate::connector::builder::MeowithConnectorBuilder;
+use crate::connector::headers::{extract_filename, path_basename};
+use crate::connector::nodes::NodePool;
+use crate::connector::retry::RetryPolicy;
+use crate::dto::range::{construct_pagination_query, ContentRange, DownloadRange, Range};
 use crate::dto::request::{
     DeleteDirectoryRequest, RenameEntityRequest, UploadSessionRequest, UploadSessionResumeRequest,
 };
@@ -7,41 +10,217 @@ use crate::dto::response::{
     BucketDto, Entity, EntityList, FileResponse, UploadSessionResumeResponse,
     UploadSessionStartResponse,
 };
-use crate::error::ConnectorError::{Local, Remote};
-use crate::error::{ConnectorError, ConnectorResponse, NodeClientError};
+use crate::error::{ConnectorError, ConnectorResponse, NodeClientError, RequestContext};
+use futures_util::TryStreamExt;
+use mime::{Mime, APPLICATION_OCTET_STREAM};
 use reqwest::header::{
-    HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_TYPE, RANGE,
+    HeaderValue, AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_LENGTH, CONTENT_RANGE, CONTENT_TYPE,
+    RANGE,
 };
-use reqwest::{Body, Client, ClientBuilder};
+use reqwest::{Body, Client, Method, RequestBuilder, Response, StatusCode};
+use std::io;
 use std::str::FromStr;
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct MeowithConnector {
     client: Client,
+    auth: HeaderValue,
     bucket_id: Uuid,
     app_id: Uuid,
-    node_addr: String,
+    nodes: Arc<NodePool>,
+    retry: RetryPolicy,
+    durable_threshold: u64,
 }
 
 const CONTENT_LENGTH_HEADER: &str = "X-File-Content-Length";
 
+/// Whether a request may be sent again to another node after it possibly reached the first one.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Idempotency {
+    /// Fails over on connection errors, timeouts and 5xx responses.
+    Idempotent,
+    /// Only fails over when the connection could not be established.
+    NonIdempotent,
+}
+
+use Idempotency::{Idempotent, NonIdempotent};
+
 impl MeowithConnector {
+    /// # Panics
+    ///
+    /// When the token is not a valid header value or the http client fails to initialize.
+    /// Use [`MeowithConnector::builder`] to handle these errors.
     pub fn new(token: &str, bucket_id: Uuid, app_id: Uuid, node_addr: String) -> Self {
-        let mut headers = HeaderMap::new();
-        headers.insert(
-            AUTHORIZATION,
-            HeaderValue::from_str(format!("Bearer {}", token).as_str()).unwrap(),
-        );
+        Self::builder(token, bucket_id, app_id, node_addr)
+            .build()
+            .expect("Failed to build the connector")
+    }
+
+    pub fn builder(
+        token: &str,
+        bucket_id: Uuid,
+        app_id: Uuid,
+        node_addr: String,
+    ) -> MeowithConnectorBuilder {
+        MeowithConnectorBuilder::new(token, bucket_id, app_id, node_addr)
+    }
 
+    pub(crate) fn from_parts(
+        client: Client,
+        auth: HeaderValue,
+        bucket_id: Uuid,
+        app_id: Uuid,
+        nodes: NodePool,
+        retry: RetryPolicy,
+        durable_threshold: u64,
+    ) -> Self {
         Self {
-            client: ClientBuilder::new()
-                .default_headers(headers)
-                .build()
-                .unwrap(),
+            client,
+            auth,
             bucket_id,
             app_id,
-            node_addr,
+            nodes: Arc::new(nodes),
+            retry,
+            durable_threshold,
+        }
+    }
+
+    /// Addresses of the nodes which did not fail recently.
+    pub fn healthy_nodes(&self) -> Vec<String> {
+        self.nodes.healthy_addrs()
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry
+    }
+
+    /// Size in bytes above which uploads go through a durable session.
+    pub fn durable_threshold(&self) -> u64 {
+        self.durable_threshold
+    }
+
+    fn request(&self, method: Method, url: String) -> RequestBuilder {
+        self.client
+            .request(method, url)
+            .header(AUTHORIZATION, self.auth.clone())
+    }
+
+    /// Sends the request built by `build` for the node address, failing over to the next node when it's down.
+    /// Idempotent requests are retried according to the connector's [`RetryPolicy`].
+    ///
+    /// Returns the response only if its status is successful.
+    async fn execute<F>(
+        &self,
+        context: &RequestContext,
+        idempotency: Idempotency,
+        build: F,
+    ) -> ConnectorResponse<Response>
+    where
+        F: Fn(&str) -> RequestBuilder,
+    {
+        let mut attempt = 1;
+        loop {
+            let delay = match self.execute_attempt(context, idempotency, &build).await {
+                Err(err)
+                    if idempotency == Idempotent
+                        && attempt < self.retry.max_attempts
+                        && self.retry.is_retryable(&err) =>
+                {
+                    self.retry.backoff(attempt)
+                }
+                result => return result,
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+
+    async fn execute_attempt<F>(
+        &self,
+        context: &RequestContext,
+        idempotency: Idempotency,
+        build: &F,
+    ) -> ConnectorResponse<Response>
+    where
+        F: Fn(&str) -> RequestBuilder,
+    {
+        let candidates = self.nodes.candidates();
+        let last = candidates.len() - 1;
+        for (i, node) in candidates.into_iter().enumerate() {
+            let request = build(node.addr())
+                .build()
+                .map_err(|err| ConnectorError::from(err).with_context(context.clone()))?;
+            let context = context.clone().request(request.method(), request.url());
+            match self.client.execute(request).await {
+                Ok(response) if response.status().is_success() => {
+                    node.mark_up();
+                    return Ok(response);
+                }
+                Ok(response) if response.status().is_server_error() => {
+                    node.mark_down();
+                    if idempotency == NonIdempotent || i == last {
+                        return Err(ConnectorError::from_response(response)
+                            .await
+                            .with_context(context));
+                    }
+                }
+                Ok(response) => {
+                    node.mark_up();
+                    return Err(ConnectorError::from_response(response)
+                        .await
+                        .with_context(context));
+                }
+                Err(err) if err.is_connect() || err.is_timeout() => {
+                    node.mark_down();
+                    let retry = err.is_connect() || idempotency == Idempotent;
+                    if !retry || i == last {
+                        return Err(ConnectorError::from(err).with_context(context));
+                    }
+                }
+                Err(err) => return Err(ConnectorError::from(err).with_context(context)),
+            }
+        }
+        unreachable!("the node pool is never empty")
+    }
+
+    /// Sends a request which can not be rebuilt, such as one with a streamed body, to the first healthy node.
+    async fn execute_once<F>(
+        &self,
+        context: &RequestContext,
+        build: F,
+    ) -> ConnectorResponse<Response>
+    where
+        F: FnOnce(&str) -> RequestBuilder,
+    {
+        let candidates = self.nodes.candidates();
+        let node = candidates[0];
+        let request = build(node.addr())
+            .build()
+            .map_err(|err| ConnectorError::from(err).with_context(context.clone()))?;
+        let context = context.clone().request(request.method(), request.url());
+        match self.client.execute(request).await {
+            Ok(response) if response.status().is_success() => {
+                node.mark_up();
+                Ok(response)
+            }
+            Ok(response) => {
+                if response.status().is_server_error() {
+                    node.mark_down();
+                } else {
+                    node.mark_up();
+                }
+                Err(ConnectorError::from_response(response)
+                    .await
+                    .with_context(context))
+            }
+            Err(err) => {
+                if err.is_connect() || err.is_timeout() {
+                    node.mark_down();
+                }
+                Err(ConnectorError::from(err).with_context(context))
+            }
         }
     }
 
@@ -51,66 +230,61 @@ impl MeowithConnector {
         path: &str,
         size: u64,
     ) -> ConnectorResponse<()> {
-        let response = self
-            .client
-            .post(format!(
-                "{}/api/file/upload/oneshot/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(path)
-            ))
+        let context = RequestContext::new("upload_oneshot", Some(path));
+        self.execute_once(&context, |node| {
+            self.request(
+                Method::POST,
+                format!(
+                    "{}/api/file/upload/oneshot/{}/{}/{}",
+                    node,
+                    self.app_id,
+                    self.bucket_id,
+                    urlencoding::encode(path)
+                ),
+            )
             .header(CONTENT_LENGTH, size.to_string())
             .body(stream)
-            .send()
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(ConnectorError::Remote(
-                NodeClientError::from(response).await,
-            ));
-        }
+        })
+        .await?;
         Ok(())
     }
 
     pub async fn delete_file(&self, path: &str) -> ConnectorResponse<()> {
-        let response = self
-            .client
-            .delete(format!(
-                "{}/api/file/delete/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(path)
-            ))
-            .send()
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
+        let context = RequestContext::new("delete_file", Some(path));
+        self.execute(&context, NonIdempotent, |node| {
+            self.request(
+                Method::DELETE,
+                format!(
+                    "{}/api/file/delete/{}/{}/{}",
+                    node,
+                    self.app_id,
+                    self.bucket_id,
+                    urlencoding::encode(path)
+                ),
+            )
+        })
+        .await?;
         Ok(())
     }
 
     pub async fn rename_file(&self, from: &str, to: &str) -> ConnectorResponse<()> {
+        let context = RequestContext::new("rename_file", Some(from));
         let req = RenameEntityRequest { to: to.to_string() };
 
-        let response = self
-            .client
-            .post(format!(
-                "{}/api/file/rename/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(from)
-            ))
+        self.execute(&context, NonIdempotent, |node| {
+            self.request(
+                Method::POST,
+                format!(
+                    "{}/api/file/rename/{}/{}/{}",
+                    node,
+                    self.app_id,
+                    self.bucket_id,
+                    urlencoding::encode(from)
+                ),
+            )
             .json(&req)
-            .send()
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
+        })
+        .await?;
         Ok(())
     }
 
@@ -119,46 +293,66 @@ impl MeowithConnector {
         path: &str,
         range: DownloadRange,
     ) -> ConnectorResponse<FileResponse> {
-        let mut response = self.client.get(format!(
-            "{}/api/file/download/{}/{}/{}",
-            self.node_addr,
-            self.app_id,
-            self.bucket_id,
-            urlencoding::encode(path)
-        ));
-        if !range.is_full() {
-            response = response.header(RANGE, range.header_value());
-        }
-        let response = response.send().await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
+        let context = RequestContext::new("download_file_range", Some(path));
+        let response = self
+            .execute(&context, Idempotent, |node| {
+                let request = self.request(
+                    Method::GET,
+                    format!(
+                        "{}/api/file/download/{}/{}/{}",
+                        node,
+                        self.app_id,
+                        self.bucket_id,
+                        urlencoding::encode(path)
+                    ),
+                );
+                if range.is_full() {
+                    request
+                } else {
+                    request.header(RANGE, range.header_value())
+                }
+            })
+            .await?;
 
-        Ok(FileResponse {
-            length: response
-                .headers()
-                .get(CONTENT_LENGTH_HEADER)
-                .ok_or(Local(Box::new(NodeClientError::BadRequest)))?
-                .to_str()?
-                .to_string()
-                .parse::<u64>()?,
-            name: extract_filename(
-                response
-                    .headers()
-                    .get(CONTENT_DISPOSITION)
-                    .ok_or(Local(Box::new(NodeClientError::BadRequest)))?
-                    .to_str()?,
-            )
-            .ok_or(Local(Box::new(NodeClientError::BadRequest)))?,
-            mime: response
-                .headers()
-                .get(CONTENT_TYPE)
-                .ok_or(Local(Box::new(NodeClientError::BadRequest)))?
-                .to_str()?
-                .to_string(),
-            response,
-        })
+        let headers = response.headers();
+        let length = headers
+            .get(CONTENT_LENGTH_HEADER)
+            .and_then(|length| length.to_str().ok())
+            .and_then(|length| length.parse::<u64>().ok())
+            .ok_or_else(|| {
+                ConnectorError::local(NodeClientError::BadRequest).with_context(context.clone())
+            })?;
+        let name = headers
+            .get(CONTENT_DISPOSITION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(extract_filename)
+            .unwrap_or_else(|| path_basename(path));
+        let mime = headers
+            .get(CONTENT_TYPE)
+            .and_then(|mime| mime.to_str().ok())
+            .and_then(|mime| mime.parse::<Mime>().ok())
+            .unwrap_or(APPLICATION_OCTET_STREAM);
+        let content_range = match response.status() {
+            StatusCode::PARTIAL_CONTENT => Some(
+                headers
+                    .get(CONTENT_RANGE)
+                    .and_then(|range| range.to_str().ok())
+                    .and_then(ContentRange::parse)
+                    .ok_or_else(|| {
+                        ConnectorError::local(NodeClientError::BadRequest)
+                            .with_context(context.clone())
+                    })?,
+            ),
+            _ => None,
+        };
+
+        Ok(FileResponse::new(
+            length,
+            name,
+            mime,
+            content_range,
+            Box::pin(response.bytes_stream().map_err(io::Error::other)),
+        ))
     }
 
     pub async fn download_file(&self, path: &str) -> ConnectorResponse<FileResponse> {
@@ -166,114 +360,107 @@ impl MeowithConnector {
     }
 
     pub async fn create_directory(&self, path: &str) -> ConnectorResponse<()> {
-        let response = self
-            .client
-            .post(format!(
-                "{}/api/directory/create/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(path)
-            ))
-            .send()
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
+        let context = RequestContext::new("create_directory", Some(path));
+        self.execute(&context, NonIdempotent, |node| {
+            self.request(
+                Method::POST,
+                format!(
+                    "{}/api/directory/create/{}/{}/{}",
+                    node,
+                    self.app_id,
+                    self.bucket_id,
+                    urlencoding::encode(path)
+                ),
+            )
+        })
+        .await?;
         Ok(())
     }
 
     pub async fn rename_directory(&self, from: &str, to: &str) -> ConnectorResponse<()> {
+        let context = RequestContext::new("rename_directory", Some(from));
         let req = RenameEntityRequest { to: to.to_string() };
 
-        let response = self
-            .client
-            .post(format!(
-                "{}/api/directory/rename/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(from)
-            ))
+        self.execute(&context, NonIdempotent, |node| {
+            self.request(
+                Method::POST,
+                format!(
+                    "{}/api/directory/rename/{}/{}/{}",
+                    node,
+                    self.app_id,
+                    self.bucket_id,
+                    urlencoding::encode(from)
+                ),
+            )
             .json(&req)
-            .send()
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
+        })
+        .await?;
         Ok(())
     }
 
     pub async fn delete_directory(&self, path: &str, recursive: bool) -> ConnectorResponse<()> {
+        let context = RequestContext::new("delete_directory", Some(path));
         let req = DeleteDirectoryRequest { recursive };
-        let response = self
-            .client
-            .delete(format!(
-                "{}/api/directory/delete/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(path)
-            ))
+        self.execute(&context, NonIdempotent, |node| {
+            self.request(
+                Method::DELETE,
+                format!(
+                    "{}/api/directory/delete/{}/{}/{}",
+                    node,
+                    self.app_id,
+                    self.bucket_id,
+                    urlencoding::encode(path)
+                ),
+            )
             .json(&req)
-            .send()
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
+        })
+        .await?;
         Ok(())
     }
 
     pub async fn list_bucket_files(&self, range: Option<Range>) -> ConnectorResponse<EntityList> {
+        let context = RequestContext::new("list_bucket_files", None);
+        let query = construct_pagination_query(range);
         let response = self
-            .client
-            .get(format!(
-                "{}/api/bucket/list/files/{}/{}{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                construct_pagination_query(range)
-            ))
-            .send()
+            .execute(&context, Idempotent, |node| {
+                self.request(
+                    Method::GET,
+                    format!(
+                        "{}/api/bucket/list/files/{}/{}{}",
+                        node, self.app_id, self.bucket_id, query
+                    ),
+                )
+            })
             .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
 
         response
             .json::<EntityList>()
             .await
-            .map_err(ConnectorError::from)
+            .map_err(|err| ConnectorError::from(err).with_context(context))
     }
 
     pub async fn list_bucket_directories(
         &self,
         range: Option<Range>,
     ) -> ConnectorResponse<EntityList> {
+        let context = RequestContext::new("list_bucket_directories", None);
+        let query = construct_pagination_query(range);
         let response = self
-            .client
-            .get(format!(
-                "{}/api/bucket/list/directories/{}/{}{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                construct_pagination_query(range)
-            ))
-            .send()
+            .execute(&context, Idempotent, |node| {
+                self.request(
+                    Method::GET,
+                    format!(
+                        "{}/api/bucket/list/directories/{}/{}{}",
+                        node, self.app_id, self.bucket_id, query
+                    ),
+                )
+            })
             .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
 
         response
             .json::<EntityList>()
             .await
-            .map_err(ConnectorError::from)
+            .map_err(|err| ConnectorError::from(err).with_context(context))
     }
 
     pub async fn list_directory(
@@ -281,70 +468,71 @@ impl MeowithConnector {
         path: &str,
         range: Option<Range>,
     ) -> ConnectorResponse<EntityList> {
+        let context = RequestContext::new("list_directory", Some(path));
+        let query = construct_pagination_query(range);
         let response = self
-            .client
-            .get(format!(
-                "{}/api/directory/list/{}/{}/{}{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(path),
-                construct_pagination_query(range)
-            ))
-            .send()
+            .execute(&context, Idempotent, |node| {
+                self.request(
+                    Method::GET,
+                    format!(
+                        "{}/api/directory/list/{}/{}/{}{}",
+                        node,
+                        self.app_id,
+                        self.bucket_id,
+                        urlencoding::encode(path),
+                        query
+                    ),
+                )
+            })
             .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
 
         response
             .json::<EntityList>()
             .await
-            .map_err(ConnectorError::from)
+            .map_err(|err| ConnectorError::from(err).with_context(context))
     }
 
     pub async fn stat_resource(&self, path: &str) -> ConnectorResponse<Entity> {
+        let context = RequestContext::new("stat_resource", Some(path));
         let response = self
-            .client
-            .get(format!(
-                "{}/api/bucket/stat/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(path)
-            ))
-            .send()
+            .execute(&context, Idempotent, |node| {
+                self.request(
+                    Method::GET,
+                    format!(
+                        "{}/api/bucket/stat/{}/{}/{}",
+                        node,
+                        self.app_id,
+                        self.bucket_id,
+                        urlencoding::encode(path)
+                    ),
+                )
+            })
             .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
 
         response
             .json::<Entity>()
             .await
-            .map_err(ConnectorError::from)
+            .map_err(|err| ConnectorError::from(err).with_context(context))
     }
 
     pub async fn fetch_bucket_info(&self) -> ConnectorResponse<BucketDto> {
+        let context = RequestContext::new("fetch_bucket_info", None);
         let response = self
-            .client
-            .get(format!(
-                "{}/api/bucket/info/{}/{}",
-                self.node_addr, self.app_id, self.bucket_id
-            ))
-            .send()
+            .execute(&context, Idempotent, |node| {
+                self.request(
+                    Method::GET,
+                    format!(
+                        "{}/api/bucket/info/{}/{}",
+                        node, self.app_id, self.bucket_id
+                    ),
+                )
+            })
             .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
 
         response
             .json::<BucketDto>()
             .await
-            .map_err(ConnectorError::from)
+            .map_err(|err| ConnectorError::from(err).with_context(context))
     }
 
     pub async fn start_upload_session(
@@ -352,55 +540,56 @@ impl MeowithConnector {
         path: &str,
         size: u64,
     ) -> ConnectorResponse<UploadSessionStartResponse> {
+        let context = RequestContext::new("start_upload_session", Some(path));
         let req = UploadSessionRequest { size };
         let response = self
-            .client
-            .delete(format!(
-                "{}/api/file/upload/durable/{}/{}/{}",
-                self.node_addr,
-                self.app_id,
-                self.bucket_id,
-                urlencoding::encode(path)
-            ))
-            .json(&req)
-            .send()
+            .execute(&context, NonIdempotent, |node| {
+                self.request(
+                    Method::POST,
+                    format!(
+                        "{}/api/file/upload/durable/{}/{}/{}",
+                        node,
+                        self.app_id,
+                        self.bucket_id,
+                        urlencoding::encode(path)
+                    ),
+                )
+                .json(&req)
+            })
             .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
 
         response
             .json::<UploadSessionStartResponse>()
             .await
-            .map_err(ConnectorError::from)
+            .map_err(|err| ConnectorError::from(err).with_context(context))
     }
 
+    /// Fetches how much of the file the session received, safe to repeat.
     pub async fn resume_upload_session(
         &self,
         session: UploadSessionStartResponse,
     ) -> ConnectorResponse<UploadSessionResumeResponse> {
+        let context = RequestContext::new("resume_upload_session", None);
         let req = UploadSessionResumeRequest {
             session_id: Uuid::from_str(session.code.as_str())?,
         };
         let response = self
-            .client
-            .delete(format!(
-                "{}/api/file/upload/resume/{}/{}",
-                self.node_addr, self.app_id, self.bucket_id
-            ))
-            .json(&req)
-            .send()
+            .execute(&context, Idempotent, |node| {
+                self.request(
+                    Method::POST,
+                    format!(
+                        "{}/api/file/upload/resume/{}/{}",
+                        node, self.app_id, self.bucket_id
+                    ),
+                )
+                .json(&req)
+            })
             .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
 
         response
             .json::<UploadSessionResumeResponse>()
             .await
-            .map_err(ConnectorError::from)
+            .map_err(|err| ConnectorError::from(err).with_context(context))
     }
 
     pub async fn put_file(
@@ -408,23 +597,19 @@ impl MeowithConnector {
         session: UploadSessionStartResponse,
         stream: Body,
     ) -> ConnectorResponse<()> {
-        let req = UploadSessionResumeRequest {
-            session_id: Uuid::from_str(session.code.as_str())?,
-        };
-        let response = self
-            .client
-            .delete(format!(
-                "{}/api/file/upload/put/{}/{}/{}",
-                self.node_addr, self.app_id, self.bucket_id, session.code
-            ))
-            .json(&req)
+        let context = RequestContext::new("put_file", None);
+        let session_id = Uuid::from_str(session.code.as_str())?;
+        self.execute_once(&context, |node| {
+            self.request(
+                Method::PUT,
+                format!(
+                    "{}/api/file/upload/put/{}/{}/{}",
+                    node, self.app_id, self.bucket_id, session_id
+                ),
+            )
             .body(stream)
-            .send()
-            .await?;
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Remote(NodeClientError::from(response).await));
-        }
+        })
+        .await?;
 
         Ok(())
     }
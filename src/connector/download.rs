@@ -0,0 +1,292 @@
+use crate::connector::connector::MeowithConnector;
+use crate::connector::progress::{ProgressReporter, ProgressTracker};
+use crate::dto::range::DownloadRange;
+use crate::error::{ConnectorError, ConnectorResponse, NodeClientError};
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt, TryStreamExt};
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::fs::{File, OpenOptions};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+
+/// How a file is split up by the segmented downloads.
+#[derive(Clone, Debug)]
+pub struct SegmentOptions {
+    /// Number of ranges the file is split into.
+    pub segments: u64,
+    /// Maximum number of ranges downloaded at once.
+    pub concurrency: usize,
+}
+
+impl Default for SegmentOptions {
+    fn default() -> Self {
+        Self {
+            segments: 8,
+            concurrency: 4,
+        }
+    }
+}
+
+impl SegmentOptions {
+    /// The inclusive byte ranges of the segments of a file of the given size, in order.
+    /// The last one is shorter when the size doesn't divide evenly.
+    pub fn ranges(&self, size: u64) -> Vec<(u64, u64)> {
+        if size == 0 {
+            return Vec::new();
+        }
+        let segment_len = size.div_ceil(self.segments.max(1));
+        (0..size)
+            .step_by(segment_len as usize)
+            .map(|start| (start, (start + segment_len).min(size) - 1))
+            .collect()
+    }
+}
+
+impl MeowithConnector {
+    /// Downloads the file to `local`, returning its size.
+    ///
+    /// The data is written to `<local>.part` first, which is renamed into place once its size
+    /// matches the remote file. Dropped connections are resumed from the bytes already on disk,
+    /// including ones left behind by a previous, unfinished download.
+    pub async fn download_to_path(
+        &self,
+        remote: &str,
+        local: impl AsRef<Path>,
+    ) -> ConnectorResponse<u64> {
+        self.download_to_path_tracked(remote, local.as_ref(), None)
+            .await
+            .map_err(|err| err.in_operation("download_to_path", Some(remote)))
+    }
+
+    pub async fn download_to_path_with_progress(
+        &self,
+        remote: &str,
+        local: impl AsRef<Path>,
+        progress: &ProgressReporter,
+    ) -> ConnectorResponse<u64> {
+        self.download_to_path_tracked(remote, local.as_ref(), Some(progress))
+            .await
+            .map_err(|err| err.in_operation("download_to_path", Some(remote)))
+    }
+
+    async fn download_to_path_tracked(
+        &self,
+        remote: &str,
+        local: &Path,
+        progress: Option<&ProgressReporter>,
+    ) -> ConnectorResponse<u64> {
+        let part = part_path(local);
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&part)
+            .await?;
+        let mut written = file.metadata().await?.len();
+
+        let retry = self.retry_policy();
+        let mut tracker = None;
+        let mut attempt = 1;
+        let length = loop {
+            let range = if written == 0 {
+                DownloadRange::full()
+            } else {
+                DownloadRange::new(Some(written), None)
+            };
+            let response = {
+                let result = self.download_file_range(remote, range).await;
+                match result {
+                    Err(err)
+                        if written > 0
+                            && err.remote_error() == Some(&NodeClientError::RangeUnsatisfiable) =>
+                    {
+                        None
+                    }
+                    result => Some(result?),
+                }
+            };
+            let Some(response) = response else {
+                // The part file is already complete, or longer than the remote file.
+                let size = self.stat_resource(remote).await?.size;
+                if size == written {
+                    break size;
+                }
+                file = File::create(&part).await?;
+                written = 0;
+                continue;
+            };
+            let length = response.length;
+            match response.content_range {
+                Some(range) if range.start == written => {}
+                None if written == 0 => {}
+                None => {
+                    // The node ignored the range and sent the whole file, start over.
+                    file = File::create(&part).await?;
+                    written = 0;
+                }
+                Some(_) if written == 0 => {
+                    return Err(ConnectorError::local(NodeClientError::BadRequest));
+                }
+                Some(_) => {
+                    // The node sent another range than the one on disk continues with.
+                    file = File::create(&part).await?;
+                    written = 0;
+                    continue;
+                }
+            }
+            let tracker = tracker.get_or_insert_with(|| {
+                progress.map(|progress| ProgressTracker::new(progress.clone(), Some(length)))
+            });
+
+            let before = written;
+            let mut stream = match tracker {
+                Some(tracker) => tracker.wrap(response, written).boxed(),
+                None => response.boxed(),
+            };
+            let mut interrupted = None;
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Ok(chunk) => {
+                        file.write_all(&chunk).await?;
+                        written += chunk.len() as u64;
+                    }
+                    Err(err) => {
+                        interrupted = Some(err);
+                        break;
+                    }
+                }
+            }
+            let Some(err) = interrupted else {
+                break length;
+            };
+
+            if written > before {
+                // Bytes reached the disk since the last attempt, so the attempts count again.
+                attempt = 1;
+            } else if attempt >= retry.max_attempts {
+                return Err(err.into());
+            } else {
+                attempt += 1;
+            }
+            file.flush().await?;
+            tokio::time::sleep(retry.backoff(attempt)).await;
+        };
+
+        file.flush().await?;
+        file.sync_all().await?;
+        drop(file);
+        if written != length {
+            return Err(ConnectorError::local(NodeClientError::BadRequest));
+        }
+        tokio::fs::rename(&part, local).await?;
+        Ok(length)
+    }
+}
+
+impl MeowithConnector {
+    /// Downloads the file as consecutive ranges fetched concurrently, yielding them in order.
+    ///
+    /// Each segment is buffered in memory until it's yielded, so at most
+    /// `concurrency` segments are held at once.
+    pub async fn download_segmented(
+        &self,
+        remote: &str,
+        options: SegmentOptions,
+    ) -> ConnectorResponse<impl Stream<Item = ConnectorResponse<Bytes>> + Send + '_> {
+        let size = self.stat_resource(remote).await?.size;
+        let remote = remote.to_string();
+        Ok(stream::iter(options.ranges(size))
+            .map(move |(start, end)| {
+                let remote = remote.clone();
+                async move { self.fetch_segment(&remote, start, end, size).await }
+            })
+            .buffered(options.concurrency.max(1)))
+    }
+
+    /// Downloads the file as concurrently fetched ranges, written in place into `<local>.part`,
+    /// which is renamed to `local` once complete. Returns the size of the file.
+    pub async fn download_segmented_to_path(
+        &self,
+        remote: &str,
+        local: impl AsRef<Path>,
+        options: SegmentOptions,
+    ) -> ConnectorResponse<u64> {
+        self.download_segmented_to_path_inner(remote, local.as_ref(), options)
+            .await
+            .map_err(|err| err.in_operation("download_segmented_to_path", Some(remote)))
+    }
+
+    async fn download_segmented_to_path_inner(
+        &self,
+        remote: &str,
+        local: &Path,
+        options: SegmentOptions,
+    ) -> ConnectorResponse<u64> {
+        let size = self.stat_resource(remote).await?.size;
+        let part = part_path(local);
+        File::create(&part).await?.set_len(size).await?;
+
+        let written = AtomicU64::new(0);
+        stream::iter(options.ranges(size))
+            .map(Ok::<_, ConnectorError>)
+            .try_for_each_concurrent(options.concurrency.max(1), |(start, end)| {
+                let (part, written) = (&part, &written);
+                async move {
+                    let segment = self.fetch_segment(remote, start, end, size).await?;
+                    let mut file = OpenOptions::new().write(true).open(part).await?;
+                    file.seek(SeekFrom::Start(start)).await?;
+                    file.write_all(&segment).await?;
+                    file.sync_all().await?;
+                    written.fetch_add(segment.len() as u64, Ordering::Relaxed);
+                    Ok(())
+                }
+            })
+            .await?;
+
+        if written.into_inner() != size {
+            return Err(ConnectorError::local(NodeClientError::BadRequest));
+        }
+        tokio::fs::rename(&part, local).await?;
+        Ok(size)
+    }
+
+    /// Fetches the inclusive range of a file of the given size, retrying when the body is cut off.
+    async fn fetch_segment(
+        &self,
+        remote: &str,
+        start: u64,
+        end: u64,
+        size: u64,
+    ) -> ConnectorResponse<Bytes> {
+        let retry = self.retry_policy();
+        let mut attempt = 1;
+        loop {
+            let response = self
+                .download_file_range(remote, DownloadRange::new(Some(start), Some(end)))
+                .await?;
+            // Anything else than the requested range of the same file would end up in the wrong place.
+            let matches = response.content_range.is_some_and(|range| {
+                range.start == start
+                    && range.end == end
+                    && range.total.is_none_or(|total| total == size)
+            });
+            if !matches {
+                return Err(ConnectorError::local(NodeClientError::BadRequest));
+            }
+            let delay = match response.bytes().await {
+                Ok(segment) if segment.len() as u64 == end - start + 1 => return Ok(segment),
+                Ok(_) => return Err(ConnectorError::local(NodeClientError::BadRequest)),
+                Err(err) if attempt >= retry.max_attempts => return Err(err),
+                Err(_) => retry.backoff(attempt),
+            };
+            tokio::time::sleep(delay).await;
+            attempt += 1;
+        }
+    }
+}
+
+fn part_path(local: &Path) -> PathBuf {
+    let mut part = local.as_os_str().to_owned();
+    part.push(".part");
+    PathBuf::from(part)
+}
@@ -1,10 +1,166 @@
+use std::borrow::Cow;
+
+/// A parsed `Content-Disposition` header, following RFC 6266.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentDisposition {
+    /// The disposition type, lowercased, usually `attachment` or `inline`.
+    pub disposition: String,
+    /// The decoded `filename*` parameter, or `filename` when it's missing or can not be decoded.
+    pub filename: Option<String>,
+}
+
+impl ContentDisposition {
+    pub fn parse(header: &str) -> Option<Self> {
+        let mut parser = Parser {
+            input: header.as_bytes(),
+            pos: 0,
+        };
+        parser.skip_whitespace();
+        let disposition = parser.token()?.to_ascii_lowercase();
+
+        let mut filename = None;
+        let mut ext_filename = None;
+        loop {
+            parser.skip_whitespace();
+            if parser.eof() {
+                break;
+            }
+            if !parser.consume(b';') {
+                // Tolerate garbage between the parameters.
+                parser.skip_until(b';');
+                continue;
+            }
+            parser.skip_whitespace();
+            let Some(name) = parser.token() else {
+                parser.skip_until(b';');
+                continue;
+            };
+            parser.skip_whitespace();
+            if !parser.consume(b'=') {
+                continue;
+            }
+            parser.skip_whitespace();
+            let value = parser.value();
+
+            match name.to_ascii_lowercase().as_str() {
+                "filename" if filename.is_none() => filename = Some(value),
+                "filename*" if ext_filename.is_none() => ext_filename = decode_ext_value(&value),
+                _ => {}
+            }
+        }
+
+        Some(Self {
+            disposition,
+            filename: ext_filename.or(filename),
+        })
+    }
+}
+
+/// Extracts the file name from a `Content-Disposition` header value.
 pub fn extract_filename(content_disposition: &str) -> Option<String> {
-    if let Some(start) = content_disposition.find("filename=") {
-        let filename_start = start + "filename=".len();
-        let filename = &content_disposition[filename_start..];
+    ContentDisposition::parse(content_disposition)?
+        .filename
+        .filter(|name| !name.is_empty())
+}
+
+/// The last non empty segment of a remote path, used when the node does not name the file.
+pub fn path_basename(path: &str) -> String {
+    path.rsplit('/')
+        .find(|segment| !segment.is_empty())
+        .unwrap_or(path)
+        .to_string()
+}
+
+/// Decodes an RFC 5987 `ext-value`, like `UTF-8'en'%E2%82%AC%20rates`.
+fn decode_ext_value(value: &str) -> Option<String> {
+    let mut parts = value.splitn(3, '\'');
+    let charset = parts.next()?;
+    let _language = parts.next()?;
+    let encoded = parts.next()?;
+
+    let bytes: Cow<[u8]> = urlencoding::decode_binary(encoded.as_bytes());
+    if charset.eq_ignore_ascii_case("utf-8") {
+        String::from_utf8(bytes.into_owned()).ok()
+    } else if charset.eq_ignore_ascii_case("iso-8859-1") {
+        Some(bytes.iter().map(|&byte| byte as char).collect())
+    } else {
+        None
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
 
-        let filename = filename.trim_matches('"');
-        return Some(filename.to_string());
+impl Parser<'_> {
+    fn eof(&self) -> bool {
+        self.pos >= self.input.len()
     }
-    None
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.pos).copied()
+    }
+
+    fn consume(&mut self, byte: u8) -> bool {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t')) {
+            self.pos += 1;
+        }
+    }
+
+    fn skip_until(&mut self, byte: u8) {
+        while self.peek().is_some_and(|it| it != byte) {
+            self.pos += 1;
+        }
+    }
+
+    fn token(&mut self) -> Option<String> {
+        let start = self.pos;
+        while self.peek().is_some_and(is_token_char) {
+            self.pos += 1;
+        }
+        (self.pos > start).then(|| self.slice(start, self.pos))
+    }
+
+    /// A quoted string with its escapes removed, or everything up to the next `;`.
+    fn value(&mut self) -> String {
+        if !self.consume(b'"') {
+            let start = self.pos;
+            self.skip_until(b';');
+            return self.slice(start, self.pos).trim_end().to_string();
+        }
+
+        let mut value = Vec::new();
+        while let Some(byte) = self.peek() {
+            self.pos += 1;
+            match byte {
+                b'"' => break,
+                b'\\' => {
+                    if let Some(escaped) = self.peek() {
+                        value.push(escaped);
+                        self.pos += 1;
+                    }
+                }
+                _ => value.push(byte),
+            }
+        }
+        String::from_utf8_lossy(&value).into_owned()
+    }
+
+    fn slice(&self, start: usize, end: usize) -> String {
+        String::from_utf8_lossy(&self.input[start..end]).into_owned()
+    }
+}
+
+fn is_token_char(byte: u8) -> bool {
+    byte.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&byte)
 }
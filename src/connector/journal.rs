@@ -0,0 +1,179 @@
+use crate::connector::connector::MeowithConnector;
+use crate::connector::upload::UploadSource;
+use crate::dto::response::UploadSessionStartResponse;
+use crate::error::{ConnectorError, ConnectorResponse, NodeClientError};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use tokio::fs::{self, File};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::Mutex;
+
+/// A durable upload session which has not been completed yet.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PendingUpload {
+    /// Remote path of the uploaded file
+    pub path: String,
+    /// Entry size in bytes
+    pub size: u64,
+    /// The session code, see [`UploadSessionStartResponse::code`]
+    pub code: String,
+    /// Identifies the uploaded data, so that it can be opened again after a restart.
+    /// For example the local file path.
+    pub source_id: String,
+    pub validity: u32,
+    pub started: DateTime<Utc>,
+}
+
+/// Persists pending upload sessions, so that they can be resumed by another process.
+pub trait SessionStore: Send + Sync {
+    /// Records the upload, replacing any previous record with the same code.
+    fn save(&self, upload: &PendingUpload) -> impl Future<Output = ConnectorResponse<()>> + Send;
+
+    fn remove(&self, code: &str) -> impl Future<Output = ConnectorResponse<()>> + Send;
+
+    fn pending(&self) -> impl Future<Output = ConnectorResponse<Vec<PendingUpload>>> + Send;
+}
+
+/// Stores the pending uploads as a json array in a single file.
+pub struct JsonFileSessionStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl JsonFileSessionStore {
+    /// The file is created on the first save.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    async fn read(&self) -> ConnectorResponse<Vec<PendingUpload>> {
+        match fs::read(&self.path).await {
+            Ok(data) => Ok(serde_json::from_slice(&data)?),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    /// Writes through a synced temporary file, so that a crash never leaves a truncated journal behind.
+    async fn write(&self, uploads: &[PendingUpload]) -> ConnectorResponse<()> {
+        let mut tmp = self.path.clone().into_os_string();
+        tmp.push(".tmp");
+        let mut file = File::create(&tmp).await?;
+        file.write_all(&serde_json::to_vec(uploads)?).await?;
+        file.sync_all().await?;
+        drop(file);
+        fs::rename(&tmp, &self.path).await?;
+        self.sync_directory().await
+    }
+
+    /// Makes the rename itself durable.
+    #[cfg(unix)]
+    async fn sync_directory(&self) -> ConnectorResponse<()> {
+        let dir = match self.path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => std::path::Path::new("."),
+        };
+        File::open(dir).await?.sync_all().await?;
+        Ok(())
+    }
+
+    #[cfg(not(unix))]
+    async fn sync_directory(&self) -> ConnectorResponse<()> {
+        Ok(())
+    }
+}
+
+impl SessionStore for JsonFileSessionStore {
+    async fn save(&self, upload: &PendingUpload) -> ConnectorResponse<()> {
+        let _guard = self.lock.lock().await;
+        let mut uploads = self.read().await?;
+        uploads.retain(|it| it.code != upload.code);
+        uploads.push(upload.clone());
+        self.write(&uploads).await
+    }
+
+    async fn remove(&self, code: &str) -> ConnectorResponse<()> {
+        let _guard = self.lock.lock().await;
+        let mut uploads = self.read().await?;
+        uploads.retain(|it| it.code != code);
+        self.write(&uploads).await
+    }
+
+    async fn pending(&self) -> ConnectorResponse<Vec<PendingUpload>> {
+        let _guard = self.lock.lock().await;
+        self.read().await
+    }
+}
+
+impl MeowithConnector {
+    /// Same as [`MeowithConnector::upload_durable`], but keeps the session in the store until the
+    /// upload completes.
+    pub async fn upload_durable_journaled<S: UploadSource, J: SessionStore>(
+        &self,
+        path: &str,
+        source: &S,
+        source_id: &str,
+        store: &J,
+    ) -> ConnectorResponse<()> {
+        let session = self.start_upload_session(path, source.size()).await?;
+        store
+            .save(&PendingUpload {
+                path: path.to_string(),
+                size: source.size(),
+                code: session.code.clone(),
+                source_id: source_id.to_string(),
+                validity: session.validity,
+                started: Utc::now(),
+            })
+            .await?;
+
+        let code = session.code.clone();
+        self.continue_durable_upload(session, source).await?;
+        store.remove(&code).await
+    }
+
+    /// Resumes an upload listed by [`SessionStore::pending`], with the source identified by its `source_id`.
+    ///
+    /// The upload is removed from the store once it completes or the node no longer knows the session.
+    pub async fn resume_pending_upload<S: UploadSource, J: SessionStore>(
+        &self,
+        upload: &PendingUpload,
+        source: &S,
+        store: &J,
+    ) -> ConnectorResponse<()> {
+        if source.size() != upload.size {
+            return Err(ConnectorError::local(NodeClientError::BadRequest));
+        }
+
+        let mut session = UploadSessionStartResponse {
+            code: upload.code.clone(),
+            validity: upload.validity,
+            uploaded: 0,
+        };
+        let result = async {
+            session.uploaded = self
+                .resume_upload_session(session.clone())
+                .await?
+                .uploaded_size;
+            self.continue_durable_upload(session, source).await
+        }
+        .await;
+
+        let finished = match &result {
+            Ok(()) => true,
+            Err(err) => err
+                .remote_error()
+                .is_some_and(NodeClientError::is_not_found),
+        };
+        if finished {
+            store.remove(&upload.code).await?;
+        }
+        result
+    }
+}
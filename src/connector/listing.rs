@@ -0,0 +1,81 @@
+use crate::connector::connector::MeowithConnector;
+use crate::dto::range::Range;
+use crate::dto::response::{Entity, EntityList};
+use crate::error::{ConnectorError, ConnectorResponse};
+use futures_util::{stream, Stream, TryStreamExt};
+use std::future::Future;
+
+/// Number of entities fetched per request by the listing streams.
+pub const DEFAULT_PAGE_SIZE: i32 = 1000;
+
+impl MeowithConnector {
+    /// Streams all the files of the bucket, fetching `page_size` entities at a time.
+    pub fn list_bucket_files_stream(
+        &self,
+        page_size: i32,
+    ) -> impl Stream<Item = ConnectorResponse<Entity>> + Send + '_ {
+        paginate(page_size, move |range| self.list_bucket_files(Some(range)))
+    }
+
+    /// Streams all the directories of the bucket, fetching `page_size` entities at a time.
+    pub fn list_bucket_directories_stream(
+        &self,
+        page_size: i32,
+    ) -> impl Stream<Item = ConnectorResponse<Entity>> + Send + '_ {
+        paginate(page_size, move |range| {
+            self.list_bucket_directories(Some(range))
+        })
+    }
+
+    /// Streams the whole content of the directory, fetching `page_size` entities at a time.
+    pub fn list_directory_stream<'a>(
+        &'a self,
+        path: &'a str,
+        page_size: i32,
+    ) -> impl Stream<Item = ConnectorResponse<Entity>> + Send + 'a {
+        paginate(page_size, move |range| {
+            self.list_directory(path, Some(range))
+        })
+    }
+}
+
+/// Requests consecutive pages until one comes back shorter than `page_size`.
+/// The end of each requested range is exclusive.
+fn paginate<'a, F, Fut>(
+    page_size: i32,
+    fetch: F,
+) -> impl Stream<Item = ConnectorResponse<Entity>> + Send + 'a
+where
+    F: Fn(Range) -> Fut + Send + 'a,
+    Fut: Future<Output = ConnectorResponse<EntityList>> + Send + 'a,
+{
+    let page_size = page_size.max(1);
+    stream::try_unfold(Some(0), move |offset: Option<i32>| {
+        let page = offset.map(|offset| {
+            let range = Range {
+                start: Some(offset),
+                end: Some(offset.checked_add(page_size).unwrap_or(i32::MAX)),
+            };
+            (offset, fetch(range))
+        });
+        async move {
+            let Some((offset, page)) = page else {
+                return Ok::<_, ConnectorError>(None);
+            };
+            let entities = page.await?.entities;
+            let next = next_offset(offset, page_size, entities.len());
+            Ok(Some((entities, next)))
+        }
+    })
+    .map_ok(|entities| stream::iter(entities.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+/// The offset of the page after a page with `fetched` entities, `None` once the listing ended
+/// or the offset can't grow any further.
+fn next_offset(offset: i32, page_size: i32, fetched: usize) -> Option<i32> {
+    if fetched < page_size as usize {
+        return None;
+    }
+    offset.checked_add(page_size)
+}
@@ -1,2 +1,14 @@
+pub mod admin;
+pub mod builder;
+#[allow(clippy::module_inception)]
 pub mod connector;
-mod headers;
+pub mod download;
+pub mod headers;
+pub mod journal;
+pub mod listing;
+pub mod nodes;
+pub mod progress;
+pub mod query;
+pub mod retry;
+pub mod upload;
+pub mod walk;
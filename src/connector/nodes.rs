@@ -0,0 +1,74 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// How long a failed node is skipped before it's tried first again.
+pub const DEFAULT_NODE_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// The meowith nodes a connector can talk to, along with their health.
+///
+/// Shared between the clones of a connector.
+pub(crate) struct NodePool {
+    nodes: Vec<Node>,
+    cooldown: Duration,
+}
+
+pub(crate) struct Node {
+    addr: String,
+    failed_at: Mutex<Option<Instant>>,
+}
+
+impl Node {
+    pub(crate) fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    pub(crate) fn mark_up(&self) {
+        *self.failed_at.lock().unwrap() = None;
+    }
+
+    pub(crate) fn mark_down(&self) {
+        *self.failed_at.lock().unwrap() = Some(Instant::now());
+    }
+
+    fn is_healthy(&self, cooldown: Duration) -> bool {
+        match *self.failed_at.lock().unwrap() {
+            None => true,
+            Some(failed_at) => failed_at.elapsed() >= cooldown,
+        }
+    }
+}
+
+impl NodePool {
+    pub(crate) fn new(addrs: Vec<String>, cooldown: Duration) -> Self {
+        Self {
+            nodes: addrs
+                .into_iter()
+                .map(|addr| Node {
+                    addr,
+                    failed_at: Mutex::new(None),
+                })
+                .collect(),
+            cooldown,
+        }
+    }
+
+    /// The nodes in the order they should be tried in.
+    /// Healthy nodes keep their configured order, nodes which recently failed are tried last.
+    pub(crate) fn candidates(&self) -> Vec<&Node> {
+        let (mut healthy, unhealthy): (Vec<&Node>, Vec<&Node>) = self
+            .nodes
+            .iter()
+            .partition(|node| node.is_healthy(self.cooldown));
+        healthy.extend(unhealthy);
+        healthy
+    }
+
+    /// Addresses of the nodes which are not in their cooldown.
+    pub(crate) fn healthy_addrs(&self) -> Vec<String> {
+        self.nodes
+            .iter()
+            .filter(|node| node.is_healthy(self.cooldown))
+            .map(|node| node.addr.clone())
+            .collect()
+    }
+}
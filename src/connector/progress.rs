@@ -0,0 +1,116 @@
+use bytes::Bytes;
+use futures_util::{Stream, StreamExt};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+
+/// A snapshot of a running transfer.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Progress {
+    /// Bytes transferred so far, including the ones a resumed transfer skipped.
+    pub transferred: u64,
+    /// Total bytes to transfer, when known.
+    pub total: Option<u64>,
+    /// Average bytes per second since the transfer started.
+    pub throughput: f64,
+    /// Estimated time until the transfer completes.
+    pub eta: Option<Duration>,
+}
+
+/// Receives [`Progress`] updates, one for every transferred chunk.
+#[derive(Clone)]
+pub struct ProgressReporter {
+    callback: Arc<dyn Fn(Progress) + Send + Sync>,
+}
+
+impl ProgressReporter {
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(Progress) + Send + Sync + 'static,
+    {
+        Self {
+            callback: Arc::new(callback),
+        }
+    }
+
+    /// A reporter publishing the latest progress to a watch channel.
+    pub fn watch() -> (Self, watch::Receiver<Progress>) {
+        let (tx, rx) = watch::channel(Progress::default());
+        (
+            Self::new(move |progress| {
+                let _ = tx.send(progress);
+            }),
+            rx,
+        )
+    }
+
+    pub fn report(&self, progress: Progress) {
+        (self.callback)(progress)
+    }
+
+    /// Reports the progress of a byte stream, for example before wrapping it in a request body.
+    pub fn track<S, E>(&self, stream: S, total: Option<u64>) -> impl Stream<Item = Result<Bytes, E>>
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+    {
+        ProgressTracker::new(self.clone(), total).wrap(stream, 0)
+    }
+}
+
+/// Tracks a single transfer, which may be split across several streams when it gets resumed.
+pub(crate) struct ProgressTracker {
+    reporter: ProgressReporter,
+    total: Option<u64>,
+    started: Instant,
+    sent: Arc<AtomicU64>,
+}
+
+impl ProgressTracker {
+    pub(crate) fn new(reporter: ProgressReporter, total: Option<u64>) -> Self {
+        Self {
+            reporter,
+            total,
+            started: Instant::now(),
+            sent: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Reports the chunks of a stream which starts at `offset` of the transfer.
+    pub(crate) fn wrap<S, E>(&self, stream: S, offset: u64) -> impl Stream<Item = Result<Bytes, E>>
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+    {
+        let reporter = self.reporter.clone();
+        let total = self.total;
+        let started = self.started;
+        let sent = self.sent.clone();
+        let mut position = offset;
+
+        stream.inspect(move |chunk| {
+            let Ok(chunk) = chunk else {
+                return;
+            };
+            let len = chunk.len() as u64;
+            position += len;
+            let sent = sent.fetch_add(len, Ordering::Relaxed) + len;
+
+            let elapsed = started.elapsed().as_secs_f64();
+            let throughput = if elapsed > 0.0 {
+                sent as f64 / elapsed
+            } else {
+                0.0
+            };
+            let eta = total.filter(|_| throughput > 0.0).map(|total| {
+                Duration::from_secs_f64(total.saturating_sub(position) as f64 / throughput)
+            });
+
+            reporter.report(Progress {
+                transferred: position,
+                total,
+                throughput,
+                eta,
+            });
+        })
+    }
+}
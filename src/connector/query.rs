@@ -0,0 +1,169 @@
+use crate::connector::connector::MeowithConnector;
+use crate::connector::walk::WalkOptions;
+use crate::dto::response::Entity;
+use crate::error::{ConnectorError, ConnectorResponse};
+use chrono::{DateTime, Utc};
+use futures_util::future::ready;
+use futures_util::{Stream, TryStreamExt};
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use std::ops::{Bound, RangeBounds};
+
+/// Which kinds of entities a query matches.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EntityKind {
+    #[default]
+    Any,
+    Files,
+    Directories,
+}
+
+/// Filters the entities of a bucket by path globs, size and modification time.
+///
+/// An entity matches if its path matches any of the globs, or there are none, and all the other predicates hold.
+#[derive(Clone, Debug, Default)]
+pub struct EntityQuery {
+    globs: Vec<String>,
+    size: Option<(Bound<u64>, Bound<u64>)>,
+    modified_since: Option<DateTime<Utc>>,
+    modified_before: Option<DateTime<Utc>>,
+    kind: EntityKind,
+    walk: WalkOptions,
+}
+
+impl EntityQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a glob matched against the full path, like `reports/**/*.csv`.
+    /// `*` does not match across directories, `**` does.
+    pub fn glob(mut self, pattern: &str) -> Self {
+        self.globs.push(pattern.trim_start_matches('/').to_string());
+        self
+    }
+
+    /// Limits the entity size in bytes, e.g. `1024..` or `..=4096`. An empty range like `..0`
+    /// matches nothing.
+    pub fn size(mut self, range: impl RangeBounds<u64>) -> Self {
+        self.size = Some((range.start_bound().cloned(), range.end_bound().cloned()));
+        self
+    }
+
+    /// Only entities modified at or after the given time.
+    pub fn modified_since(mut self, since: DateTime<Utc>) -> Self {
+        self.modified_since = Some(since);
+        self
+    }
+
+    /// Only entities modified strictly before the given time.
+    pub fn modified_before(mut self, before: DateTime<Utc>) -> Self {
+        self.modified_before = Some(before);
+        self
+    }
+
+    pub fn kind(mut self, kind: EntityKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// Options of the underlying walk, its `max_depth` is derived from the globs.
+    pub fn walk_options(mut self, walk: WalkOptions) -> Self {
+        self.walk = walk;
+        self
+    }
+
+    /// Checks everything but the globs.
+    pub fn matches_entity(&self, entity: &Entity) -> bool {
+        let kind = match self.kind {
+            EntityKind::Any => true,
+            EntityKind::Files => !entity.is_dir,
+            EntityKind::Directories => entity.is_dir,
+        };
+        kind && self.size.is_none_or(|size| size.contains(&entity.size))
+            && self
+                .modified_since
+                .is_none_or(|since| entity.last_modified >= since)
+            && self
+                .modified_before
+                .is_none_or(|before| entity.last_modified < before)
+    }
+
+    fn glob_set(&self) -> ConnectorResponse<GlobSet> {
+        let mut builder = GlobSetBuilder::new();
+        for pattern in &self.globs {
+            let glob = GlobBuilder::new(pattern)
+                .literal_separator(true)
+                .build()
+                .map_err(ConnectorError::local)?;
+            builder.add(glob);
+        }
+        builder.build().map_err(ConnectorError::local)
+    }
+
+    /// The deepest directory containing every match, and how deep below it matches can be.
+    fn walk_root(&self) -> (String, Option<usize>) {
+        let split: Vec<(Vec<&str>, Vec<&str>)> = self
+            .globs
+            .iter()
+            .map(|pattern| {
+                let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+                let literal = segments
+                    .iter()
+                    .take_while(|segment| !is_glob_segment(segment))
+                    .count()
+                    // The last segment names the entity itself, not a directory to walk.
+                    .min(segments.len().saturating_sub(1));
+                let (prefix, rest) = segments.split_at(literal);
+                (prefix.to_vec(), rest.to_vec())
+            })
+            .collect();
+        if split.is_empty() {
+            return (String::new(), None);
+        }
+
+        let mut root = split[0].0.clone();
+        for (prefix, _) in &split[1..] {
+            let common = root.iter().zip(prefix).take_while(|(a, b)| a == b).count();
+            root.truncate(common);
+        }
+
+        let mut max_depth = Some(0);
+        for (prefix, rest) in &split {
+            if rest.iter().any(|segment| segment.contains("**")) {
+                max_depth = None;
+                break;
+            }
+            let depth = prefix.len() - root.len() + rest.len();
+            max_depth = max_depth.map(|max: usize| max.max(depth));
+        }
+        (root.join("/"), max_depth)
+    }
+}
+
+fn is_glob_segment(segment: &str) -> bool {
+    segment.contains(['*', '?', '[', '{', '\\'])
+}
+
+impl MeowithConnector {
+    /// Lazily walks the bucket, yielding the entities matching the query with their full paths.
+    ///
+    /// Only the directories which can contain matches are listed, starting at the longest literal
+    /// prefix of the globs.
+    pub fn query<'a>(
+        &'a self,
+        query: &EntityQuery,
+    ) -> ConnectorResponse<impl Stream<Item = ConnectorResponse<(String, Entity)>> + Send + 'a>
+    {
+        let globs = query.glob_set()?;
+        let (root, max_depth) = query.walk_root();
+        let options = WalkOptions {
+            max_depth,
+            ..query.walk.clone()
+        };
+        let query = query.clone();
+
+        Ok(self.walk(&root, options).try_filter(move |(path, entity)| {
+            ready((globs.is_empty() || globs.is_match(path)) && query.matches_entity(entity))
+        }))
+    }
+}
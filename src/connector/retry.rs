@@ -0,0 +1,105 @@
+use crate::error::{ConnectorError, NodeClientError};
+use rand::Rng;
+use std::time::Duration;
+
+/// Controls how often and how fast idempotent requests are retried.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    /// Total number of attempts, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry.
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each retry.
+    pub multiplier: f64,
+    /// Fraction of the delay which is randomized, between 0 and 1.
+    pub jitter: f64,
+    /// Remote errors worth retrying, transport errors are always retried.
+    ///
+    /// [`NodeClientError::Unknown`] errors are matched by the error their status stands for,
+    /// see [`NodeClientError::known`]. `Unknown` entries match any error with the same status.
+    pub retryable_errors: Vec<NodeClientError>,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2.0,
+            jitter: 0.5,
+            retryable_errors: vec![
+                NodeClientError::InternalError,
+                NodeClientError::TooManyRequests,
+                NodeClientError::Unavailable,
+            ],
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Never retries.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::default()
+        }
+    }
+
+    /// The delay before the given retry, counting from 1.
+    ///
+    /// Never exceeds `max_backoff`. A negative or NaN `multiplier` is treated as 1 and a NaN
+    /// `jitter` as 0.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let multiplier = if self.multiplier >= 0.0 {
+            self.multiplier
+        } else {
+            1.0
+        };
+        // Computed in seconds, as the growing delay easily overflows a `Duration` before it's capped.
+        let seconds = self.initial_backoff.as_secs_f64() * multiplier.powi(exponent);
+        let delay = if seconds.is_nan() {
+            Duration::ZERO
+        } else if seconds >= self.max_backoff.as_secs_f64() {
+            self.max_backoff
+        } else {
+            Duration::from_secs_f64(seconds)
+        };
+
+        let jitter = if self.jitter.is_nan() {
+            0.0
+        } else {
+            self.jitter.clamp(0.0, 1.0)
+        };
+        if jitter == 0.0 {
+            return delay;
+        }
+        delay.mul_f64(1.0 - jitter * rand::thread_rng().gen::<f64>())
+    }
+
+    pub fn is_retryable(&self, error: &ConnectorError) -> bool {
+        match error {
+            ConnectorError::Remote { error, status, .. } => {
+                let status = match error {
+                    NodeClientError::Unknown { status, .. } => status.or(*status),
+                    _ => *status,
+                };
+                let known = error.known();
+                self.retryable_errors
+                    .iter()
+                    .any(|retryable| match retryable {
+                        NodeClientError::Unknown {
+                            status: Some(retryable),
+                            ..
+                        } => status == Some(*retryable),
+                        retryable => known.as_ref() == Some(retryable),
+                    })
+            }
+            ConnectorError::Local { source, .. } => source
+                .downcast_ref::<reqwest::Error>()
+                .is_some_and(|err| err.is_connect() || err.is_timeout() || err.is_request()),
+        }
+    }
+}
@@ -0,0 +1,318 @@
+use crate::connector::connector::MeowithConnector;
+use crate::connector::progress::{ProgressReporter, ProgressTracker};
+use crate::dto::response::UploadSessionStartResponse;
+use crate::error::{ConnectorError, ConnectorResponse, NodeClientError};
+use bytes::Bytes;
+use futures_util::{stream, Stream, StreamExt};
+use reqwest::Body;
+use std::error::Error;
+use std::future::Future;
+use std::io;
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+use std::pin::{pin, Pin};
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use tokio::fs::File;
+use tokio::io::{AsyncRead, AsyncSeekExt, AsyncWriteExt};
+use tokio_util::io::ReaderStream;
+
+/// Files larger than this are uploaded through a durable session by default.
+pub const DEFAULT_DURABLE_THRESHOLD: u64 = 64 * 1024 * 1024;
+
+pub type ByteStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
+
+/// Data which can be read again starting at any offset, so that an interrupted upload can continue
+/// where the node left off.
+pub trait UploadSource: Send + Sync {
+    /// Total size in bytes.
+    fn size(&self) -> u64;
+
+    /// Opens the source, skipping the first `offset` bytes.
+    fn open_at(&self, offset: u64) -> impl Future<Output = ConnectorResponse<ByteStream>> + Send;
+}
+
+impl UploadSource for Bytes {
+    fn size(&self) -> u64 {
+        self.len() as u64
+    }
+
+    fn open_at(&self, offset: u64) -> impl Future<Output = ConnectorResponse<ByteStream>> + Send {
+        let chunk = self.slice((offset as usize).min(self.len())..);
+        async move { Ok(Box::pin(stream::once(async move { Ok(chunk) })) as ByteStream) }
+    }
+}
+
+/// A local file, streamed from disk.
+pub struct FileSource {
+    path: PathBuf,
+    size: u64,
+}
+
+impl FileSource {
+    /// Reads the size of the file, it must not change until the upload completes.
+    pub async fn open(path: impl AsRef<Path>) -> ConnectorResponse<Self> {
+        let path = path.as_ref().to_path_buf();
+        let size = tokio::fs::metadata(&path).await?.len();
+        Ok(Self { path, size })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl UploadSource for FileSource {
+    fn size(&self) -> u64 {
+        self.size
+    }
+
+    async fn open_at(&self, offset: u64) -> ConnectorResponse<ByteStream> {
+        let mut file = File::open(&self.path).await?;
+        file.seek(SeekFrom::Start(offset)).await?;
+        Ok(Box::pin(ReaderStream::new(file)))
+    }
+}
+
+/// A stream written out to a temporary file, which is removed on drop.
+struct SpooledSource {
+    // Keeps the file alive for as long as the source is used.
+    _file: NamedTempFile,
+    source: FileSource,
+}
+
+impl SpooledSource {
+    async fn spool<S, E>(stream: S) -> ConnectorResponse<Self>
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+        E: Into<Box<dyn Error + Send + Sync>>,
+    {
+        let temp = NamedTempFile::new()?;
+        let mut file = File::from_std(temp.reopen()?);
+        let mut stream = pin!(stream);
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(ConnectorError::local)?;
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+
+        let source = FileSource::open(temp.path()).await?;
+        Ok(Self {
+            _file: temp,
+            source,
+        })
+    }
+}
+
+impl MeowithConnector {
+    /// Uploads a local file without loading it into memory.
+    ///
+    /// Files above the connector's durable threshold go through [`MeowithConnector::upload_durable`],
+    /// smaller ones through [`MeowithConnector::upload_oneshot`].
+    pub async fn upload_file(
+        &self,
+        local_path: impl AsRef<Path>,
+        remote_path: &str,
+    ) -> ConnectorResponse<()> {
+        let source = FileSource::open(local_path)
+            .await
+            .map_err(|err| err.in_operation("upload_file", Some(remote_path)))?;
+        self.upload_source(remote_path, &source, None).await
+    }
+
+    pub async fn upload_file_with_progress(
+        &self,
+        local_path: impl AsRef<Path>,
+        remote_path: &str,
+        progress: &ProgressReporter,
+    ) -> ConnectorResponse<()> {
+        let source = FileSource::open(local_path)
+            .await
+            .map_err(|err| err.in_operation("upload_file", Some(remote_path)))?;
+        let tracker = ProgressTracker::new(progress.clone(), Some(source.size()));
+        self.upload_source(remote_path, &source, Some(&tracker))
+            .await
+    }
+
+    /// Uploads everything read from the reader, see [`MeowithConnector::upload_stream`].
+    pub async fn upload_reader<R>(
+        &self,
+        path: &str,
+        reader: R,
+        size: Option<u64>,
+    ) -> ConnectorResponse<()>
+    where
+        R: AsyncRead + Send + 'static,
+    {
+        self.upload_stream(path, ReaderStream::new(reader), size)
+            .await
+    }
+
+    /// Uploads the stream, with `size` being its total length when known upfront.
+    ///
+    /// Streams of a known size up to the durable threshold are sent directly in one request.
+    /// Larger streams and ones of unknown size are first spooled to a temporary file, so that
+    /// the size is known and a durable upload can read them again after an interruption.
+    pub async fn upload_stream<S, E>(
+        &self,
+        path: &str,
+        stream: S,
+        size: Option<u64>,
+    ) -> ConnectorResponse<()>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn Error + Send + Sync>> + 'static,
+    {
+        self.upload_stream_tracked(path, stream, size, None).await
+    }
+
+    /// Same as [`MeowithConnector::upload_stream`], only the upload itself is reported, not the spooling.
+    pub async fn upload_stream_with_progress<S, E>(
+        &self,
+        path: &str,
+        stream: S,
+        size: Option<u64>,
+        progress: &ProgressReporter,
+    ) -> ConnectorResponse<()>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn Error + Send + Sync>> + 'static,
+    {
+        self.upload_stream_tracked(path, stream, size, Some(progress))
+            .await
+    }
+
+    async fn upload_stream_tracked<S, E>(
+        &self,
+        path: &str,
+        stream: S,
+        size: Option<u64>,
+        progress: Option<&ProgressReporter>,
+    ) -> ConnectorResponse<()>
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn Error + Send + Sync>> + 'static,
+    {
+        match size {
+            Some(size) if size <= self.durable_threshold() => {
+                let body = match progress {
+                    Some(progress) => Body::wrap_stream(progress.track(stream, Some(size))),
+                    None => Body::wrap_stream(stream),
+                };
+                self.upload_oneshot(body, path, size).await
+            }
+            _ => {
+                let spooled = SpooledSource::spool(stream)
+                    .await
+                    .map_err(|err| err.in_operation("upload_stream", Some(path)))?;
+                if size.is_some_and(|size| size != spooled.source.size()) {
+                    return Err(ConnectorError::local(NodeClientError::BadRequest)
+                        .in_operation("upload_stream", Some(path)));
+                }
+                let tracker = progress.map(|progress| {
+                    ProgressTracker::new(progress.clone(), Some(spooled.source.size()))
+                });
+                self.upload_source(path, &spooled.source, tracker.as_ref())
+                    .await
+            }
+        }
+    }
+
+    /// Picks the upload method based on the size of the source.
+    pub(crate) async fn upload_source<S: UploadSource>(
+        &self,
+        path: &str,
+        source: &S,
+        tracker: Option<&ProgressTracker>,
+    ) -> ConnectorResponse<()> {
+        if source.size() > self.durable_threshold() {
+            let session = self.start_upload_session(path, source.size()).await?;
+            return self.drive_durable_upload(session, source, tracker).await;
+        }
+        let stream = source
+            .open_at(0)
+            .await
+            .map_err(|err| err.in_operation("upload_file", Some(path)))?;
+        self.upload_oneshot(tracked_body(stream, 0, tracker), path, source.size())
+            .await
+    }
+
+    /// Uploads the source through a durable upload session.
+    ///
+    /// When the transfer is interrupted, the session is resumed and the upload continues from the
+    /// offset reported by the node, for as long as the session is valid.
+    pub async fn upload_durable<S: UploadSource>(
+        &self,
+        path: &str,
+        source: &S,
+    ) -> ConnectorResponse<()> {
+        let session = self.start_upload_session(path, source.size()).await?;
+        self.drive_durable_upload(session, source, None).await
+    }
+
+    pub async fn upload_durable_with_progress<S: UploadSource>(
+        &self,
+        path: &str,
+        source: &S,
+        progress: &ProgressReporter,
+    ) -> ConnectorResponse<()> {
+        let session = self.start_upload_session(path, source.size()).await?;
+        let tracker = ProgressTracker::new(progress.clone(), Some(source.size()));
+        self.drive_durable_upload(session, source, Some(&tracker))
+            .await
+    }
+
+    /// Streams the rest of the source to an already started session.
+    pub async fn continue_durable_upload<S: UploadSource>(
+        &self,
+        session: UploadSessionStartResponse,
+        source: &S,
+    ) -> ConnectorResponse<()> {
+        self.drive_durable_upload(session, source, None).await
+    }
+
+    async fn drive_durable_upload<S: UploadSource>(
+        &self,
+        session: UploadSessionStartResponse,
+        source: &S,
+        tracker: Option<&ProgressTracker>,
+    ) -> ConnectorResponse<()> {
+        let validity = Duration::from_secs(session.validity as u64);
+        let retry = self.retry_policy();
+        let mut uploaded = session.uploaded;
+        let mut attempt = 1;
+        loop {
+            let stream = source
+                .open_at(uploaded)
+                .await
+                .map_err(|err| err.in_operation("upload_durable", None))?;
+            let body = tracked_body(stream, uploaded, tracker);
+            let delay = match self.put_file(session.clone(), body).await {
+                Ok(()) => return Ok(()),
+                Err(err) if attempt < retry.max_attempts && retry.is_retryable(&err) => {
+                    retry.backoff(attempt)
+                }
+                Err(err) => return Err(err),
+            };
+            // Waiting out the whole validity would let the session expire, whether it's still
+            // valid is left to the node to answer.
+            tokio::time::sleep(delay.min(validity / 2)).await;
+
+            let resumed = self.resume_upload_session(session.clone()).await?;
+            if resumed.uploaded_size > uploaded {
+                // The node stored more of the file, so the attempts count from the start again.
+                attempt = 1;
+            } else {
+                attempt += 1;
+            }
+            uploaded = resumed.uploaded_size;
+        }
+    }
+}
+
+fn tracked_body(stream: ByteStream, offset: u64, tracker: Option<&ProgressTracker>) -> Body {
+    match tracker {
+        Some(tracker) => Body::wrap_stream(tracker.wrap(stream, offset)),
+        None => Body::wrap_stream(stream),
+    }
+}
@@ -0,0 +1,221 @@
+use crate::connector::connector::MeowithConnector;
+use crate::connector::listing::DEFAULT_PAGE_SIZE;
+use crate::dto::response::Entity;
+use crate::error::ConnectorResponse;
+use futures_util::future::BoxFuture;
+use futures_util::stream::{FuturesOrdered, FuturesUnordered};
+use futures_util::{stream, FutureExt, Stream, StreamExt, TryStreamExt};
+use std::collections::VecDeque;
+
+/// The order in which [`MeowithConnector::walk`] yields the entries.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum WalkOrder {
+    /// Entries are yielded as soon as their directory is listed.
+    #[default]
+    Unordered,
+    /// Level by level, each directory in the order returned by the node.
+    BreadthFirst,
+    /// Depth first, with the entries of each directory in name order. A directory's content comes
+    /// right after it, so `a/x` is yielded before `a-b` even though `a-b` sorts first as a path.
+    /// Directories are listed one at a time.
+    Sorted,
+}
+
+#[derive(Clone, Debug)]
+pub struct WalkOptions {
+    /// How deep to descend, entries directly in the walked directory have a depth of 1.
+    /// `Some(0)` yields nothing and `None` walks the whole subtree.
+    pub max_depth: Option<usize>,
+    /// Maximum number of directories listed at once.
+    pub concurrency: usize,
+    pub order: WalkOrder,
+    pub page_size: i32,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            max_depth: None,
+            concurrency: 8,
+            order: WalkOrder::default(),
+            page_size: DEFAULT_PAGE_SIZE,
+        }
+    }
+}
+
+impl MeowithConnector {
+    /// Recursively lists the directory, yielding every entry below it with its full path.
+    ///
+    /// The stream ends after the first error.
+    pub fn walk<'a>(
+        &'a self,
+        path: &str,
+        options: WalkOptions,
+    ) -> impl Stream<Item = ConnectorResponse<(String, Entity)>> + Send + 'a {
+        let listings = match options.order {
+            WalkOrder::Unordered => Listings::Unordered(FuturesUnordered::new()),
+            _ => Listings::Ordered(FuturesOrdered::new()),
+        };
+        let mut pending = VecDeque::new();
+        if options.max_depth != Some(0) {
+            pending.push_back((path.to_string(), 1));
+        }
+        let walker = Walker {
+            connector: self,
+            options,
+            pending,
+            listings,
+            ready: VecDeque::new(),
+            frames: Vec::new(),
+            done: false,
+        };
+        stream::unfold(walker, |mut walker| async move {
+            let item = walker.next().await?;
+            Some((item, walker))
+        })
+    }
+}
+
+/// A listed directory: its path, the depth of its entries and the entries.
+type Listing = (String, usize, Vec<Entity>);
+
+enum Listings<'a> {
+    Ordered(FuturesOrdered<BoxFuture<'a, ConnectorResponse<Listing>>>),
+    Unordered(FuturesUnordered<BoxFuture<'a, ConnectorResponse<Listing>>>),
+}
+
+impl<'a> Listings<'a> {
+    fn len(&self) -> usize {
+        match self {
+            Listings::Ordered(listings) => listings.len(),
+            Listings::Unordered(listings) => listings.len(),
+        }
+    }
+
+    fn push(&mut self, listing: BoxFuture<'a, ConnectorResponse<Listing>>) {
+        match self {
+            Listings::Ordered(listings) => listings.push_back(listing),
+            Listings::Unordered(listings) => listings.push(listing),
+        }
+    }
+
+    async fn next(&mut self) -> Option<ConnectorResponse<Listing>> {
+        match self {
+            Listings::Ordered(listings) => listings.next().await,
+            Listings::Unordered(listings) => listings.next().await,
+        }
+    }
+}
+
+struct Walker<'a> {
+    connector: &'a MeowithConnector,
+    options: WalkOptions,
+    /// Directories waiting to be listed, with the depth of their entries.
+    pending: VecDeque<(String, usize)>,
+    listings: Listings<'a>,
+    ready: VecDeque<(String, Entity)>,
+    /// The not yet visited entries of each directory on the current path, used by the sorted walk.
+    frames: Vec<VecDeque<(String, Entity, usize)>>,
+    done: bool,
+}
+
+impl<'a> Walker<'a> {
+    async fn next(&mut self) -> Option<ConnectorResponse<(String, Entity)>> {
+        if self.done {
+            return None;
+        }
+        let item = match self.options.order {
+            WalkOrder::Sorted => self.next_sorted().await,
+            _ => self.next_concurrent().await,
+        };
+        if !matches!(item, Some(Ok(_))) {
+            self.done = true;
+        }
+        item
+    }
+
+    async fn next_concurrent(&mut self) -> Option<ConnectorResponse<(String, Entity)>> {
+        loop {
+            if let Some(entry) = self.ready.pop_front() {
+                return Some(Ok(entry));
+            }
+            while self.listings.len() < self.options.concurrency.max(1) {
+                let Some((dir, depth)) = self.pending.pop_front() else {
+                    break;
+                };
+                let listing = self.list(dir, depth);
+                self.listings.push(listing);
+            }
+
+            let (dir, depth, entities) = match self.listings.next().await? {
+                Ok(listing) => listing,
+                Err(err) => return Some(Err(err)),
+            };
+            for entity in entities {
+                let path = join(&dir, &entity.name);
+                if entity.is_dir && self.descends_into(depth) {
+                    self.pending.push_back((path.clone(), depth + 1));
+                }
+                self.ready.push_back((path, entity));
+            }
+        }
+    }
+
+    async fn next_sorted(&mut self) -> Option<ConnectorResponse<(String, Entity)>> {
+        if let Some((dir, depth)) = self.pending.pop_front() {
+            if let Err(err) = self.push_frame(dir, depth).await {
+                return Some(Err(err));
+            }
+        }
+        loop {
+            let frame = self.frames.last_mut()?;
+            let Some((path, entity, depth)) = frame.pop_front() else {
+                self.frames.pop();
+                continue;
+            };
+            if entity.is_dir && self.descends_into(depth) {
+                // Listed on the next call, so that the directory itself comes first.
+                self.pending.push_back((path.clone(), depth + 1));
+            }
+            return Some(Ok((path, entity)));
+        }
+    }
+
+    async fn push_frame(&mut self, dir: String, depth: usize) -> ConnectorResponse<()> {
+        let (dir, depth, mut entities) = self.list(dir, depth).await?;
+        entities.sort_by(|a, b| a.name.cmp(&b.name));
+        self.frames.push(
+            entities
+                .into_iter()
+                .map(|entity| (join(&dir, &entity.name), entity, depth))
+                .collect(),
+        );
+        Ok(())
+    }
+
+    fn descends_into(&self, depth: usize) -> bool {
+        self.options.max_depth.is_none_or(|max| depth < max)
+    }
+
+    fn list(&self, dir: String, depth: usize) -> BoxFuture<'a, ConnectorResponse<Listing>> {
+        let connector = self.connector;
+        let page_size = self.options.page_size;
+        async move {
+            let entities = connector
+                .list_directory_stream(&dir, page_size)
+                .try_collect()
+                .await?;
+            Ok((dir, depth, entities))
+        }
+        .boxed()
+    }
+}
+
+fn join(dir: &str, name: &str) -> String {
+    let dir = dir.trim_end_matches('/');
+    if dir.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}/{}", dir, name)
+    }
+}
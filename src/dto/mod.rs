@@ -1,3 +1,4 @@
+pub mod permission;
 pub mod range;
 pub mod request;
 pub mod response;
@@ -0,0 +1,66 @@
+use bitflags::bitflags;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+bitflags! {
+    /// Operations allowed on a bucket, encoded in [`ScopedPermission::allowance`](crate::dto::request::ScopedPermission::allowance).
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct BucketPermissions: u64 {
+        const READ = 1 << 0;
+        const WRITE = 1 << 1;
+        /// Allows replacing already existing files, requires `WRITE`.
+        const OVERWRITE = 1 << 2;
+        const DELETE = 1 << 3;
+        const RENAME = 1 << 4;
+        const LIST_DIRECTORY = 1 << 5;
+        const LIST_BUCKET = 1 << 6;
+        const FETCH_BUCKET_INFO = 1 << 7;
+
+        const LIST = Self::LIST_DIRECTORY.bits() | Self::LIST_BUCKET.bits();
+        const ADMIN = Self::READ.bits()
+            | Self::WRITE.bits()
+            | Self::OVERWRITE.bits()
+            | Self::DELETE.bits()
+            | Self::RENAME.bits()
+            | Self::LIST.bits()
+            | Self::FETCH_BUCKET_INFO.bits();
+    }
+}
+
+impl BucketPermissions {
+    /// Keeps unknown bits, so that permissions added by newer nodes survive a round trip.
+    pub fn from_allowance(allowance: u64) -> Self {
+        Self::from_bits_retain(allowance)
+    }
+
+    pub fn allowance(&self) -> u64 {
+        self.bits()
+    }
+}
+
+impl From<u64> for BucketPermissions {
+    fn from(value: u64) -> Self {
+        Self::from_allowance(value)
+    }
+}
+
+impl From<BucketPermissions> for u64 {
+    fn from(value: BucketPermissions) -> Self {
+        value.allowance()
+    }
+}
+
+/// Formats the flags as `READ | WRITE`, unknown bits are written in hex.
+impl Display for BucketPermissions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        bitflags::parser::to_writer(self, f)
+    }
+}
+
+impl FromStr for BucketPermissions {
+    type Err = bitflags::parser::ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        bitflags::parser::from_str(s)
+    }
+}
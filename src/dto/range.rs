@@ -48,3 +48,40 @@ impl DownloadRange {
         }
     }
 }
+
+/// The range of the file returned for a ranged download, both start and end are inclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ContentRange {
+    pub start: u64,
+    pub end: u64,
+    /// Size of the whole file, when known.
+    pub total: Option<u64>,
+}
+
+impl ContentRange {
+    /// Parses a `Content-Range` header value, like `bytes 0-99/1234` or `bytes 0-99/*`.
+    ///
+    /// Returns `None` for ranges ending before they start or past the end of the file.
+    pub fn parse(header: &str) -> Option<Self> {
+        let (range, total) = header.trim().strip_prefix("bytes ")?.split_once('/')?;
+        let (start, end) = range.split_once('-')?;
+        let total = match total {
+            "*" => None,
+            total => Some(total.parse().ok()?),
+        };
+        let range = Self {
+            start: start.parse().ok()?,
+            end: end.parse().ok()?,
+            total,
+        };
+        let valid = range.start <= range.end && range.total.is_none_or(|total| range.end < total);
+        valid.then_some(range)
+    }
+
+    /// Number of bytes in the range, zero when it ends before it starts.
+    pub fn length(&self) -> u64 {
+        self.end
+            .checked_sub(self.start)
+            .map_or(0, |length| length.saturating_add(1))
+    }
+}
@@ -1,3 +1,4 @@
+use crate::dto::permission::BucketPermissions;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -21,9 +22,75 @@ pub struct AppRolePath {
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ScopedPermission {
     pub bucket_id: Uuid,
+    /// Bits of [`BucketPermissions`]
     pub allowance: u64,
 }
 
+impl ScopedPermission {
+    pub fn new(bucket_id: Uuid, permissions: BucketPermissions) -> Self {
+        Self {
+            bucket_id,
+            allowance: permissions.allowance(),
+        }
+    }
+
+    pub fn builder(bucket_id: Uuid) -> ScopedPermissionBuilder {
+        ScopedPermissionBuilder {
+            bucket_id,
+            permissions: BucketPermissions::empty(),
+        }
+    }
+
+    pub fn permissions(&self) -> BucketPermissions {
+        BucketPermissions::from_allowance(self.allowance)
+    }
+}
+
+pub struct ScopedPermissionBuilder {
+    bucket_id: Uuid,
+    permissions: BucketPermissions,
+}
+
+impl ScopedPermissionBuilder {
+    pub fn allow(mut self, permissions: BucketPermissions) -> Self {
+        self.permissions.insert(permissions);
+        self
+    }
+
+    pub fn deny(mut self, permissions: BucketPermissions) -> Self {
+        self.permissions.remove(permissions);
+        self
+    }
+
+    pub fn read(self) -> Self {
+        self.allow(BucketPermissions::READ)
+    }
+
+    pub fn write(self) -> Self {
+        self.allow(BucketPermissions::WRITE)
+    }
+
+    pub fn delete(self) -> Self {
+        self.allow(BucketPermissions::DELETE)
+    }
+
+    pub fn rename(self) -> Self {
+        self.allow(BucketPermissions::RENAME)
+    }
+
+    pub fn list(self) -> Self {
+        self.allow(BucketPermissions::LIST)
+    }
+
+    pub fn admin(self) -> Self {
+        self.allow(BucketPermissions::ADMIN)
+    }
+
+    pub fn build(self) -> ScopedPermission {
+        ScopedPermission::new(self.bucket_id, self.permissions)
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct ModifyRoleRequest {
     pub perms: Vec<ScopedPermission>,
@@ -75,3 +142,23 @@ pub struct RenameEntityRequest {
 pub struct DeleteDirectoryRequest {
     pub recursive: bool,
 }
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateApplicationRequest {
+    pub name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateBucketRequest {
+    pub name: String,
+    /// Bucket quota in bytes
+    pub quota: i64,
+    pub encrypted: bool,
+    /// Whether uploads are only visible once fully written
+    pub atomic_upload: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EditBucketQuotaRequest {
+    pub quota: i64,
+}
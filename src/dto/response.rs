@@ -1,6 +1,19 @@
+use crate::connector::progress::ProgressReporter;
+use crate::connector::upload::ByteStream;
+use crate::dto::range::ContentRange;
+use crate::dto::request::ScopedPermission;
+use crate::error::{ConnectorError, ConnectorResponse};
+use bytes::{Bytes, BytesMut};
 use chrono::{DateTime, Utc};
-use reqwest::Response;
+use futures_util::{ready, Stream, StreamExt};
+use mime::Mime;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
 use uuid::Uuid;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -30,11 +43,18 @@ pub struct Entity {
 pub struct AppDto {
     pub id: Uuid,
     pub name: String,
+    /// Storage quota of the app in bytes
     pub quota: i64,
     pub created: DateTime<Utc>,
     pub last_modified: DateTime<Utc>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[repr(C)]
+pub struct AppList {
+    pub apps: Vec<AppDto>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[repr(C)]
 pub struct BucketDto {
@@ -50,6 +70,12 @@ pub struct BucketDto {
     pub last_modified: DateTime<Utc>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[repr(C)]
+pub struct BucketList {
+    pub buckets: Vec<BucketDto>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[repr(C)]
 pub struct UploadSessionStartResponse {
@@ -78,16 +104,160 @@ pub struct AppTokenDTO {
     pub name: String,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[repr(C)]
+pub struct TokenIssueResponse {
+    /// The issued token. It is not stored by the dashboard and can not be fetched again.
+    pub token: String,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[repr(C)]
 pub struct TokenListResponse {
     pub tokens: Vec<AppTokenDTO>,
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[repr(C)]
+pub struct RoleDto {
+    pub app_id: Uuid,
+    pub name: String,
+    pub scopes: Vec<ScopedPermission>,
+    pub created: DateTime<Utc>,
+    pub last_modified: DateTime<Utc>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[repr(C)]
+pub struct RoleList {
+    pub roles: Vec<RoleDto>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[repr(C)]
+pub struct MemberDto {
+    pub app_id: Uuid,
+    pub member_id: Uuid,
+    /// Names of the roles assigned to the member
+    pub roles: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[repr(C)]
+pub struct MemberList {
+    pub members: Vec<MemberDto>,
+}
+
+/// Upper bound of the buffer allocated upfront by [`FileResponse::bytes`].
+const MAX_PREALLOCATION: u64 = 8 * 1024 * 1024;
+
+/// A downloaded file, readable either as a [`Stream`] of chunks or through [`AsyncRead`].
 pub struct FileResponse {
+    /// Size of the whole file in bytes
     pub length: u64,
     pub name: String,
-    pub mime: String,
-    pub response: Response,
+    pub mime: Mime,
+    /// The returned part of the file, `None` when the whole file was returned.
+    pub content_range: Option<ContentRange>,
+    body: ByteStream,
+    /// The unread rest of the last chunk, used by the [`AsyncRead`] implementation.
+    chunk: Bytes,
+}
+
+impl FileResponse {
+    pub(crate) fn new(
+        length: u64,
+        name: String,
+        mime: Mime,
+        content_range: Option<ContentRange>,
+        body: ByteStream,
+    ) -> Self {
+        Self {
+            length,
+            name,
+            mime,
+            content_range,
+            body,
+            chunk: Bytes::new(),
+        }
+    }
+
+    /// Number of bytes in the body, which is the whole file unless a range was requested.
+    pub fn body_length(&self) -> u64 {
+        self.content_range
+            .map(|range| range.length())
+            .unwrap_or(self.length)
+    }
+
+    /// Reads the rest of the body into memory.
+    pub async fn bytes(mut self) -> ConnectorResponse<Bytes> {
+        // The length comes from the response headers, so it's not trusted with the allocation.
+        let capacity = self.body_length().min(MAX_PREALLOCATION);
+        let mut data = BytesMut::with_capacity(capacity as usize);
+        data.extend_from_slice(&self.chunk);
+        while let Some(chunk) = self.body.next().await {
+            data.extend_from_slice(&chunk?);
+        }
+        Ok(data.freeze())
+    }
+
+    pub async fn text(self) -> ConnectorResponse<String> {
+        let data = self.bytes().await?;
+        String::from_utf8(data.to_vec()).map_err(ConnectorError::local)
+    }
+
+    pub async fn json<T: DeserializeOwned>(self) -> ConnectorResponse<T> {
+        let data = self.bytes().await?;
+        Ok(serde_json::from_slice(&data)?)
+    }
+
+    /// Streams the body while reporting the progress, with the total being [`FileResponse::body_length`].
+    pub fn bytes_stream_with_progress(
+        self,
+        progress: &ProgressReporter,
+    ) -> impl Stream<Item = io::Result<Bytes>> {
+        let total = self.body_length();
+        progress.track(self, Some(total))
+    }
+}
+
+impl Debug for FileResponse {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileResponse")
+            .field("length", &self.length)
+            .field("name", &self.name)
+            .field("mime", &self.mime)
+            .field("content_range", &self.content_range)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Stream for FileResponse {
+    type Item = io::Result<Bytes>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if !self.chunk.is_empty() {
+            return Poll::Ready(Some(Ok(std::mem::take(&mut self.chunk))));
+        }
+        self.body.as_mut().poll_next(cx)
+    }
+}
+
+impl AsyncRead for FileResponse {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        while self.chunk.is_empty() {
+            match ready!(self.body.as_mut().poll_next(cx)) {
+                Some(Ok(chunk)) => self.chunk = chunk,
+                Some(Err(err)) => return Poll::Ready(Err(err)),
+                None => return Poll::Ready(Ok(())),
+            }
+        }
+        let len = self.chunk.len().min(buf.remaining());
+        buf.put_slice(&self.chunk.split_to(len));
+        Poll::Ready(Ok(()))
+    }
 }
@@ -1,6 +1,6 @@
-use reqwest::header::ToStrError;
-use reqwest::Response;
-use serde::Deserialize;
+use reqwest::header::{InvalidHeaderValue, ToStrError};
+use reqwest::{Method, Response, StatusCode, Url};
+use serde::{Deserialize, Deserializer};
 use std::error::Error;
 use std::fmt::{Display, Formatter};
 use std::num::ParseIntError;
@@ -12,7 +12,7 @@ pub struct ErrorResponse {
     pub code: NodeClientError,
 }
 
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub enum NodeClientError {
     InternalError,
     BadRequest,
@@ -23,59 +23,367 @@ pub enum NodeClientError {
     InsufficientStorage,
     NotEmpty,
     RangeUnsatisfiable,
+    /// The node is rate limiting the client.
+    TooManyRequests,
+    /// The node is temporarily unable to handle the request.
+    Unavailable,
+    /// An error code this version of the connector doesn't know, or a response it couldn't decode.
+    Unknown {
+        /// The `code` sent by the node, if any
+        code: Option<String>,
+        status: Option<StatusCode>,
+        body: Option<String>,
+    },
 }
 impl Error for NodeClientError {}
 
 impl Display for NodeClientError {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        f.write_str(format!("{:?}", self).as_str())
-            .expect("Something went wrong");
-        Ok(())
+        match self {
+            NodeClientError::Unknown { code, status, .. } => {
+                f.write_str("Unknown")?;
+                if let Some(code) = code {
+                    write!(f, " {}", code)?;
+                }
+                if let Some(status) = status {
+                    write!(f, " ({})", status)?;
+                }
+                Ok(())
+            }
+            known => write!(f, "{:?}", known),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeClientError {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Ok(
+            NodeClientError::from_code(&code).unwrap_or(NodeClientError::Unknown {
+                code: Some(code),
+                status: None,
+                body: None,
+            }),
+        )
     }
 }
 
 impl NodeClientError {
     pub async fn from(value: Response) -> Self {
-        match value.json::<ErrorResponse>().await {
+        let status = value.status();
+        let body = value.text().await.unwrap_or_default();
+        Self::decode(status, &body)
+    }
+
+    /// Decodes the error of a response from its body, falling back to its status when the body
+    /// doesn't carry an error code.
+    pub fn decode(status: StatusCode, body: &str) -> Self {
+        match serde_json::from_str::<ErrorResponse>(body) {
+            Ok(ErrorResponse {
+                code: NodeClientError::Unknown { code, .. },
+            }) => NodeClientError::Unknown {
+                code,
+                status: Some(status),
+                body: Some(body.to_string()),
+            },
             Ok(resp) => resp.code,
-            Err(_) => NodeClientError::InternalError,
+            Err(_) => Self::from_status(status).unwrap_or_else(|| NodeClientError::Unknown {
+                code: None,
+                status: Some(status),
+                body: (!body.is_empty()).then(|| body.to_string()),
+            }),
         }
     }
+
+    fn from_code(code: &str) -> Option<Self> {
+        Some(match code {
+            "InternalError" => NodeClientError::InternalError,
+            "BadRequest" => NodeClientError::BadRequest,
+            "NotFound" => NodeClientError::NotFound,
+            "EntityExists" => NodeClientError::EntityExists,
+            "NoSuchSession" => NodeClientError::NoSuchSession,
+            "BadAuth" => NodeClientError::BadAuth,
+            "InsufficientStorage" => NodeClientError::InsufficientStorage,
+            "NotEmpty" => NodeClientError::NotEmpty,
+            "RangeUnsatisfiable" => NodeClientError::RangeUnsatisfiable,
+            "TooManyRequests" => NodeClientError::TooManyRequests,
+            "Unavailable" => NodeClientError::Unavailable,
+            _ => return None,
+        })
+    }
+
+    fn from_status(status: StatusCode) -> Option<Self> {
+        Some(match status {
+            StatusCode::BAD_REQUEST => NodeClientError::BadRequest,
+            StatusCode::UNAUTHORIZED => NodeClientError::BadAuth,
+            StatusCode::NOT_FOUND => NodeClientError::NotFound,
+            StatusCode::PAYLOAD_TOO_LARGE => NodeClientError::InsufficientStorage,
+            StatusCode::RANGE_NOT_SATISFIABLE => NodeClientError::RangeUnsatisfiable,
+            StatusCode::TOO_MANY_REQUESTS => NodeClientError::TooManyRequests,
+            StatusCode::INTERNAL_SERVER_ERROR => NodeClientError::InternalError,
+            StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT => NodeClientError::Unavailable,
+            _ => return None,
+        })
+    }
+
+    /// The error itself, or for an unknown code the known error its HTTP status stands for.
+    /// Server errors without a more specific meaning stand for [`NodeClientError::InternalError`].
+    pub fn known(&self) -> Option<NodeClientError> {
+        match self {
+            NodeClientError::Unknown { status, .. } => status.and_then(|status| {
+                Self::from_status(status).or_else(|| {
+                    status
+                        .is_server_error()
+                        .then_some(NodeClientError::InternalError)
+                })
+            }),
+            known => Some(known.clone()),
+        }
+    }
+
+    /// Whether the same request may succeed when it's sent again later.
+    ///
+    /// Agrees with the default [`RetryPolicy`](crate::connector::retry::RetryPolicy).
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.known(),
+            Some(
+                NodeClientError::InternalError
+                    | NodeClientError::TooManyRequests
+                    | NodeClientError::Unavailable
+            )
+        )
+    }
+
+    /// Whether the resource, or the upload session, doesn't exist.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            NodeClientError::NotFound | NodeClientError::NoSuchSession => true,
+            NodeClientError::Unknown {
+                status: Some(status),
+                ..
+            } => *status == StatusCode::NOT_FOUND,
+            _ => false,
+        }
+    }
+}
+
+/// What the connector was doing when an error occurred.
+#[derive(Clone, Debug)]
+pub struct RequestContext {
+    /// Name of the connector method, like `stat_resource`
+    pub operation: &'static str,
+    /// The remote path the operation was working on
+    pub path: Option<String>,
+    pub method: Option<Method>,
+    pub url: Option<Url>,
+}
+
+impl RequestContext {
+    pub(crate) fn new(operation: &'static str, path: Option<&str>) -> Self {
+        Self {
+            operation,
+            path: path.map(str::to_string),
+            method: None,
+            url: None,
+        }
+    }
+
+    pub(crate) fn request(mut self, method: &Method, url: &Url) -> Self {
+        self.method = Some(method.clone());
+        self.url = Some(url.clone());
+        self
+    }
+}
+
+impl Display for RequestContext {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.operation)?;
+        if let Some(path) = &self.path {
+            write!(f, " of '{}'", path)?;
+        }
+        if let (Some(method), Some(url)) = (&self.method, &self.url) {
+            write!(f, " ({} {})", method, url)?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug)]
-#[allow(unused)]
 pub enum ConnectorError {
-    Remote(NodeClientError),
-    Local(Box<dyn Error>),
+    /// The node rejected the request.
+    Remote {
+        error: NodeClientError,
+        /// `None` when the error was not received in a response, like an expired upload session.
+        status: Option<StatusCode>,
+        /// The raw response body
+        body: Option<String>,
+        context: Option<Box<RequestContext>>,
+    },
+    /// The request could not be made, or the response could not be processed.
+    Local {
+        source: Box<dyn Error + Send + Sync + 'static>,
+        context: Option<Box<RequestContext>>,
+    },
 }
 
-impl From<Box<dyn Error>> for ConnectorError {
-    fn from(value: Box<dyn Error>) -> Self {
-        ConnectorError::Local(value)
+impl ConnectorError {
+    pub fn remote(error: NodeClientError) -> Self {
+        ConnectorError::Remote {
+            error,
+            status: None,
+            body: None,
+            context: None,
+        }
+    }
+
+    pub fn local<E: Into<Box<dyn Error + Send + Sync + 'static>>>(source: E) -> Self {
+        ConnectorError::Local {
+            source: source.into(),
+            context: None,
+        }
+    }
+
+    /// Reads the error out of an unsuccessful response.
+    pub async fn from_response(response: Response) -> Self {
+        let status = response.status();
+        let body = response.text().await.ok();
+        ConnectorError::Remote {
+            error: NodeClientError::decode(status, body.as_deref().unwrap_or_default()),
+            status: Some(status),
+            body,
+            context: None,
+        }
+    }
+
+    /// The error code returned by the node.
+    pub fn remote_error(&self) -> Option<&NodeClientError> {
+        match self {
+            ConnectorError::Remote { error, .. } => Some(error),
+            ConnectorError::Local { .. } => None,
+        }
+    }
+
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            ConnectorError::Remote { status, .. } => *status,
+            ConnectorError::Local { source, .. } => source
+                .downcast_ref::<reqwest::Error>()
+                .and_then(reqwest::Error::status),
+        }
+    }
+
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            ConnectorError::Remote { context, .. } | ConnectorError::Local { context, .. } => {
+                context.as_deref()
+            }
+        }
+    }
+
+    /// Attaches the context, unless the error already has one.
+    pub(crate) fn with_context(mut self, new: RequestContext) -> Self {
+        match &mut self {
+            ConnectorError::Remote { context, .. } | ConnectorError::Local { context, .. } => {
+                if context.is_none() {
+                    *context = Some(Box::new(new));
+                }
+            }
+        }
+        self
+    }
+
+    pub(crate) fn in_operation(self, operation: &'static str, path: Option<&str>) -> Self {
+        self.with_context(RequestContext::new(operation, path))
+    }
+}
+
+/// Only says what failed, the error code or the underlying error is the [`Error::source`].
+impl Display for ConnectorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.context() {
+            Some(context) => write!(f, "{}", context)?,
+            None => f.write_str("the request")?,
+        }
+        match self {
+            ConnectorError::Remote {
+                status: Some(status),
+                ..
+            } => write!(f, " was rejected by the node ({})", status),
+            ConnectorError::Remote { status: None, .. } => f.write_str(" was rejected by the node"),
+            ConnectorError::Local { .. } => f.write_str(" failed"),
+        }
+    }
+}
+
+impl Error for ConnectorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConnectorError::Remote { error, .. } => Some(error),
+            ConnectorError::Local { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+
+// Errors have to cross task boundaries and fit into `Box<dyn Error + Send + Sync>`.
+const _: () = {
+    const fn assert_send_sync<T: Error + Send + Sync + 'static>() {}
+    assert_send_sync::<ConnectorError>();
+    assert_send_sync::<NodeClientError>();
+};
+
+impl From<NodeClientError> for ConnectorError {
+    fn from(value: NodeClientError) -> Self {
+        ConnectorError::remote(value)
+    }
+}
+
+impl From<Box<dyn Error + Send + Sync>> for ConnectorError {
+    fn from(value: Box<dyn Error + Send + Sync>) -> Self {
+        ConnectorError::local(value)
     }
 }
 
 impl From<reqwest::Error> for ConnectorError {
     fn from(value: reqwest::Error) -> Self {
-        ConnectorError::Local(Box::new(value))
+        ConnectorError::local(value)
+    }
+}
+
+impl From<InvalidHeaderValue> for ConnectorError {
+    fn from(value: InvalidHeaderValue) -> Self {
+        ConnectorError::local(value)
+    }
+}
+
+impl From<std::io::Error> for ConnectorError {
+    fn from(value: std::io::Error) -> Self {
+        ConnectorError::local(value)
+    }
+}
+
+impl From<serde_json::Error> for ConnectorError {
+    fn from(value: serde_json::Error) -> Self {
+        ConnectorError::local(value)
     }
 }
 
 impl From<uuid::Error> for ConnectorError {
     fn from(value: uuid::Error) -> Self {
-        ConnectorError::Local(Box::new(value))
+        ConnectorError::local(value)
     }
 }
 
 impl From<ParseIntError> for ConnectorError {
     fn from(_: ParseIntError) -> Self {
-        ConnectorError::Remote(NodeClientError::BadRequest)
+        ConnectorError::remote(NodeClientError::BadRequest)
     }
 }
 
 impl From<ToStrError> for ConnectorError {
     fn from(_: ToStrError) -> Self {
-        ConnectorError::Remote(NodeClientError::BadRequest)
+        ConnectorError::remote(NodeClientError::BadRequest)
     }
 }
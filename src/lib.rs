@@ -1,3 +1,6 @@
 pub mod connector;
 pub mod dto;
 pub mod error;
+pub mod storage;
+#[cfg(feature = "testing")]
+pub mod testing;
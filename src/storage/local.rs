@@ -0,0 +1,265 @@
+use crate::dto::response::{Entity, FileResponse};
+use crate::error::{ConnectorError, ConnectorResponse, NodeClientError};
+use crate::storage::tree::normalize;
+use crate::storage::MeowithStorage;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use mime::APPLICATION_OCTET_STREAM;
+use std::fs::Metadata;
+use std::io;
+use std::path::{Path, PathBuf};
+use tokio::fs;
+use tokio_util::io::ReaderStream;
+
+/// A bucket backed by a directory on the local filesystem.
+///
+/// Bucket paths map to paths under the root directory, paths containing `.` or `..` segments are
+/// rejected with [`NodeClientError::BadRequest`]. Symlinks are followed as long as they stay
+/// inside the root, paths leading outside of it are rejected the same way. Entities have no
+/// directory ids.
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// The root directory has to exist already.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// The normalized bucket path along with its location on disk.
+    async fn resolve(&self, path: &str) -> ConnectorResponse<(String, PathBuf)> {
+        let path = normalize(path);
+        if path
+            .split('/')
+            .any(|segment| segment == "." || segment == "..")
+        {
+            return Err(remote(NodeClientError::BadRequest));
+        }
+        let local = self.root.join(&path);
+        self.check_contained(&local).await?;
+        Ok((path, local))
+    }
+
+    /// Fails with `BadRequest` unless the closest existing ancestor of the path, with symlinks
+    /// resolved, lies inside the root. Dangling symlinks are rejected, as writing through them
+    /// could create a file anywhere.
+    async fn check_contained(&self, local: &Path) -> ConnectorResponse<()> {
+        let root = fs::canonicalize(&self.root).await.map_err(io_error)?;
+        let mut existing = local;
+        loop {
+            match fs::canonicalize(existing).await {
+                Ok(resolved) if resolved.starts_with(&root) => return Ok(()),
+                Ok(_) => return Err(remote(NodeClientError::BadRequest)),
+                Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                    if fs::symlink_metadata(existing).await.is_ok() {
+                        return Err(remote(NodeClientError::BadRequest));
+                    }
+                    match existing.parent() {
+                        Some(parent) => existing = parent,
+                        None => return Err(remote(NodeClientError::BadRequest)),
+                    }
+                }
+                Err(err) => return Err(ConnectorError::local(err)),
+            }
+        }
+    }
+
+    /// Fails with `NotFound` unless the parent directory of the path exists.
+    async fn check_parent(&self, path: &str) -> ConnectorResponse<()> {
+        let parent = path.rsplit_once('/').map_or("", |(parent, _)| parent);
+        match metadata(&self.root.join(parent)).await? {
+            Some(metadata) if metadata.is_dir() => Ok(()),
+            _ => Err(remote(NodeClientError::NotFound)),
+        }
+    }
+
+    async fn upload_file(&self, path: &str, data: Bytes) -> ConnectorResponse<()> {
+        let (path, local) = self.resolve(path).await?;
+        if path.is_empty() || metadata(&local).await?.is_some_and(|m| m.is_dir()) {
+            return Err(remote(NodeClientError::EntityExists));
+        }
+        self.check_parent(&path).await?;
+        fs::write(&local, data).await.map_err(io_error)
+    }
+
+    async fn download_file(&self, path: &str) -> ConnectorResponse<FileResponse> {
+        let (path, local) = self.resolve(path).await?;
+        let file = match metadata(&local).await? {
+            Some(metadata) if metadata.is_file() => {
+                fs::File::open(&local).await.map_err(io_error)?
+            }
+            _ => return Err(remote(NodeClientError::NotFound)),
+        };
+        let length = file.metadata().await.map_err(io_error)?.len();
+        Ok(FileResponse::new(
+            length,
+            basename(&path).to_string(),
+            APPLICATION_OCTET_STREAM,
+            None,
+            Box::pin(ReaderStream::new(file)),
+        ))
+    }
+
+    async fn list_directory(&self, path: &str) -> ConnectorResponse<Vec<Entity>> {
+        let (_, local) = self.resolve(path).await?;
+        if !metadata(&local).await?.is_some_and(|m| m.is_dir()) {
+            return Err(remote(NodeClientError::NotFound));
+        }
+        let mut entries = fs::read_dir(&local).await.map_err(io_error)?;
+        let mut entities = Vec::new();
+        while let Some(entry) = entries.next_entry().await.map_err(io_error)? {
+            let metadata = entry.metadata().await.map_err(io_error)?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            entities.push(entity(name, &metadata));
+        }
+        entities.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entities)
+    }
+
+    async fn stat_entity(&self, path: &str) -> ConnectorResponse<Entity> {
+        let (path, local) = self.resolve(path).await?;
+        match metadata(&local).await? {
+            Some(metadata) if !path.is_empty() => {
+                Ok(entity(basename(&path).to_string(), &metadata))
+            }
+            _ => Err(remote(NodeClientError::NotFound)),
+        }
+    }
+
+    async fn rename_entity(&self, from: &str, to: &str) -> ConnectorResponse<()> {
+        let (from, local_from) = self.resolve(from).await?;
+        let (to, local_to) = self.resolve(to).await?;
+        let Some(source) = metadata(&local_from).await?.filter(|_| !from.is_empty()) else {
+            return Err(remote(NodeClientError::NotFound));
+        };
+        if to.is_empty() || metadata(&local_to).await?.is_some() {
+            return Err(remote(NodeClientError::EntityExists));
+        }
+        if source.is_dir() && to.starts_with(&format!("{from}/")) {
+            return Err(remote(NodeClientError::BadRequest));
+        }
+        self.check_parent(&to).await?;
+        fs::rename(&local_from, &local_to).await.map_err(io_error)
+    }
+
+    async fn delete_entity(&self, path: &str, recursive: bool) -> ConnectorResponse<()> {
+        let (path, local) = self.resolve(path).await?;
+        let Some(metadata) = metadata(&local).await?.filter(|_| !path.is_empty()) else {
+            return Err(remote(NodeClientError::NotFound));
+        };
+        if !metadata.is_dir() {
+            return fs::remove_file(&local).await.map_err(io_error);
+        }
+        if recursive {
+            return fs::remove_dir_all(&local).await.map_err(io_error);
+        }
+        let mut entries = fs::read_dir(&local).await.map_err(io_error)?;
+        if entries.next_entry().await.map_err(io_error)?.is_some() {
+            return Err(remote(NodeClientError::NotEmpty));
+        }
+        fs::remove_dir(&local).await.map_err(io_error)
+    }
+
+    async fn create_dir(&self, path: &str) -> ConnectorResponse<()> {
+        let (path, local) = self.resolve(path).await?;
+        if path.is_empty() || metadata(&local).await?.is_some() {
+            return Err(remote(NodeClientError::EntityExists));
+        }
+        self.check_parent(&path).await?;
+        fs::create_dir(&local).await.map_err(io_error)
+    }
+}
+
+impl MeowithStorage for LocalStorage {
+    async fn upload(&self, path: &str, data: Bytes) -> ConnectorResponse<()> {
+        self.upload_file(path, data)
+            .await
+            .map_err(|err| err.in_operation("upload", Some(path)))
+    }
+
+    async fn download(&self, path: &str) -> ConnectorResponse<FileResponse> {
+        self.download_file(path)
+            .await
+            .map_err(|err| err.in_operation("download", Some(path)))
+    }
+
+    async fn list(&self, path: &str) -> ConnectorResponse<Vec<Entity>> {
+        self.list_directory(path)
+            .await
+            .map_err(|err| err.in_operation("list", Some(path)))
+    }
+
+    async fn stat(&self, path: &str) -> ConnectorResponse<Entity> {
+        self.stat_entity(path)
+            .await
+            .map_err(|err| err.in_operation("stat", Some(path)))
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> ConnectorResponse<()> {
+        self.rename_entity(from, to)
+            .await
+            .map_err(|err| err.in_operation("rename", Some(from)))
+    }
+
+    async fn delete(&self, path: &str, recursive: bool) -> ConnectorResponse<()> {
+        self.delete_entity(path, recursive)
+            .await
+            .map_err(|err| err.in_operation("delete", Some(path)))
+    }
+
+    async fn create_directory(&self, path: &str) -> ConnectorResponse<()> {
+        self.create_dir(path)
+            .await
+            .map_err(|err| err.in_operation("create_directory", Some(path)))
+    }
+}
+
+fn remote(error: NodeClientError) -> ConnectorError {
+    ConnectorError::remote(error)
+}
+
+/// Missing entities are reported like a node would, other io errors as local ones.
+fn io_error(err: io::Error) -> ConnectorError {
+    match err.kind() {
+        io::ErrorKind::NotFound => remote(NodeClientError::NotFound),
+        _ => ConnectorError::local(err),
+    }
+}
+
+/// The metadata of the path, or `None` if nothing exists there.
+async fn metadata(path: &Path) -> ConnectorResponse<Option<Metadata>> {
+    match fs::metadata(path).await {
+        Ok(metadata) => Ok(Some(metadata)),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(ConnectorError::local(err)),
+    }
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit_once('/').map_or(path, |(_, name)| name)
+}
+
+fn entity(name: String, metadata: &Metadata) -> Entity {
+    let last_modified = metadata
+        .modified()
+        .map(DateTime::<Utc>::from)
+        .unwrap_or_else(|_| Utc::now());
+    let created = metadata
+        .created()
+        .map(DateTime::<Utc>::from)
+        .unwrap_or(last_modified);
+    Entity {
+        name,
+        dir: None,
+        dir_id: None,
+        size: if metadata.is_dir() { 0 } else { metadata.len() },
+        is_dir: metadata.is_dir(),
+        created,
+        last_modified,
+    }
+}
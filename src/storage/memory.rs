@@ -0,0 +1,101 @@
+use crate::dto::response::{Entity, FileResponse};
+use crate::error::{ConnectorError, ConnectorResponse, NodeClientError};
+use crate::storage::tree::{normalize, BucketTree};
+use crate::storage::MeowithStorage;
+use bytes::Bytes;
+use futures_util::stream;
+use mime::APPLICATION_OCTET_STREAM;
+use std::sync::Mutex;
+
+/// A bucket kept in memory, starting out empty.
+pub struct MemoryStorage {
+    tree: Mutex<BucketTree>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::with_quota(i64::MAX)
+    }
+
+    /// Uploads fail with [`NodeClientError::InsufficientStorage`] once the files would take up
+    /// more than `quota` bytes.
+    pub fn with_quota(quota: i64) -> Self {
+        Self {
+            tree: Mutex::new(BucketTree::new(quota)),
+        }
+    }
+
+    /// Total size of the stored files in bytes.
+    pub fn space_taken(&self) -> i64 {
+        self.tree.lock().unwrap().space_taken()
+    }
+
+    fn apply<T>(
+        &self,
+        operation: &'static str,
+        path: &str,
+        op: impl FnOnce(&mut BucketTree) -> Result<T, NodeClientError>,
+    ) -> ConnectorResponse<T> {
+        op(&mut self.tree.lock().unwrap())
+            .map_err(|err| ConnectorError::remote(err).in_operation(operation, Some(path)))
+    }
+}
+
+impl Default for MemoryStorage {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MeowithStorage for MemoryStorage {
+    async fn upload(&self, path: &str, data: Bytes) -> ConnectorResponse<()> {
+        self.apply("upload", path, |tree| tree.upload(path, data))
+    }
+
+    async fn download(&self, path: &str) -> ConnectorResponse<FileResponse> {
+        let data = self.apply("download", path, |tree| Ok(tree.file(path)?.data.clone()))?;
+        let path = normalize(path);
+        let name = path
+            .rsplit_once('/')
+            .map_or(path.as_str(), |(_, name)| name);
+        Ok(FileResponse::new(
+            data.len() as u64,
+            name.to_string(),
+            APPLICATION_OCTET_STREAM,
+            None,
+            Box::pin(stream::iter([Ok(data)])),
+        ))
+    }
+
+    async fn list(&self, path: &str) -> ConnectorResponse<Vec<Entity>> {
+        self.apply("list", path, |tree| tree.list_directory(path))
+    }
+
+    async fn stat(&self, path: &str) -> ConnectorResponse<Entity> {
+        self.apply("stat", path, |tree| tree.stat(path))
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> ConnectorResponse<()> {
+        self.apply("rename", from, |tree| {
+            if tree.file(from).is_ok() {
+                tree.rename_file(from, to)
+            } else {
+                tree.rename_directory(from, to)
+            }
+        })
+    }
+
+    async fn delete(&self, path: &str, recursive: bool) -> ConnectorResponse<()> {
+        self.apply("delete", path, |tree| {
+            if tree.file(path).is_ok() {
+                tree.delete_file(path)
+            } else {
+                tree.delete_directory(path, recursive)
+            }
+        })
+    }
+
+    async fn create_directory(&self, path: &str) -> ConnectorResponse<()> {
+        self.apply("create_directory", path, |tree| tree.create_directory(path))
+    }
+}
@@ -0,0 +1,85 @@
+pub mod local;
+pub mod memory;
+pub(crate) mod tree;
+
+use crate::connector::connector::MeowithConnector;
+use crate::connector::listing::DEFAULT_PAGE_SIZE;
+use crate::dto::response::{Entity, FileResponse};
+use crate::error::ConnectorResponse;
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use std::future::Future;
+
+pub use local::LocalStorage;
+pub use memory::MemoryStorage;
+
+/// The file operations of a bucket, so that code using [`MeowithConnector`] can run against
+/// [`MemoryStorage`] in unit tests or [`LocalStorage`] during local development.
+///
+/// All implementations report failures with the [`NodeClientError`](crate::error::NodeClientError)
+/// codes of a node, like `NotFound`, `EntityExists` and `NotEmpty`.
+pub trait MeowithStorage: Send + Sync {
+    /// Writes the file, replacing an existing one. The parent directory must exist.
+    fn upload(&self, path: &str, data: Bytes)
+        -> impl Future<Output = ConnectorResponse<()>> + Send;
+
+    fn download(&self, path: &str) -> impl Future<Output = ConnectorResponse<FileResponse>> + Send;
+
+    /// The whole content of the directory, the root is the empty path.
+    fn list(&self, path: &str) -> impl Future<Output = ConnectorResponse<Vec<Entity>>> + Send;
+
+    fn stat(&self, path: &str) -> impl Future<Output = ConnectorResponse<Entity>> + Send;
+
+    /// Renames a file or a directory along with its content.
+    fn rename(&self, from: &str, to: &str) -> impl Future<Output = ConnectorResponse<()>> + Send;
+
+    /// Deletes a file or a directory, which has to be empty unless `recursive` is set.
+    fn delete(
+        &self,
+        path: &str,
+        recursive: bool,
+    ) -> impl Future<Output = ConnectorResponse<()>> + Send;
+
+    /// Creates a directory, its parent must exist.
+    fn create_directory(&self, path: &str) -> impl Future<Output = ConnectorResponse<()>> + Send;
+}
+
+impl MeowithStorage for MeowithConnector {
+    async fn upload(&self, path: &str, data: Bytes) -> ConnectorResponse<()> {
+        self.upload_source(path, &data, None).await
+    }
+
+    async fn download(&self, path: &str) -> ConnectorResponse<FileResponse> {
+        self.download_file(path).await
+    }
+
+    async fn list(&self, path: &str) -> ConnectorResponse<Vec<Entity>> {
+        self.list_directory_stream(path, DEFAULT_PAGE_SIZE)
+            .try_collect()
+            .await
+    }
+
+    async fn stat(&self, path: &str) -> ConnectorResponse<Entity> {
+        self.stat_resource(path).await
+    }
+
+    async fn rename(&self, from: &str, to: &str) -> ConnectorResponse<()> {
+        if self.stat_resource(from).await?.is_dir {
+            self.rename_directory(from, to).await
+        } else {
+            self.rename_file(from, to).await
+        }
+    }
+
+    async fn delete(&self, path: &str, recursive: bool) -> ConnectorResponse<()> {
+        if self.stat_resource(path).await?.is_dir {
+            self.delete_directory(path, recursive).await
+        } else {
+            self.delete_file(path).await
+        }
+    }
+
+    async fn create_directory(&self, path: &str) -> ConnectorResponse<()> {
+        MeowithConnector::create_directory(self, path).await
+    }
+}
@@ -0,0 +1,288 @@
+use crate::dto::response::Entity;
+use crate::error::NodeClientError;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// The content of a bucket, keyed by paths without leading or trailing slashes.
+/// The root directory is the empty path and always exists.
+pub(crate) struct BucketTree {
+    quota: i64,
+    files: BTreeMap<String, FileNode>,
+    directories: BTreeMap<String, DirectoryNode>,
+}
+
+pub(crate) struct FileNode {
+    pub data: Bytes,
+    created: DateTime<Utc>,
+    last_modified: DateTime<Utc>,
+}
+
+struct DirectoryNode {
+    id: Uuid,
+    created: DateTime<Utc>,
+    last_modified: DateTime<Utc>,
+}
+
+impl BucketTree {
+    pub fn new(quota: i64) -> Self {
+        Self {
+            quota,
+            files: BTreeMap::new(),
+            directories: BTreeMap::new(),
+        }
+    }
+
+    pub fn space_taken(&self) -> i64 {
+        self.files.values().map(|file| file.data.len() as i64).sum()
+    }
+
+    #[cfg_attr(not(feature = "testing"), allow(dead_code))]
+    pub fn file_count(&self) -> i64 {
+        self.files.len() as i64
+    }
+
+    /// Checks whether a file of the given size can be written to the path.
+    pub fn check_upload(&self, path: &str, size: u64) -> Result<(), NodeClientError> {
+        let path = normalize(path);
+        if path.is_empty() || self.directories.contains_key(&path) {
+            return Err(NodeClientError::EntityExists);
+        }
+        self.check_parent(&path)?;
+        let replaced = self
+            .files
+            .get(&path)
+            .map_or(0, |file| file.data.len() as i64);
+        if self.space_taken() - replaced + size as i64 > self.quota {
+            return Err(NodeClientError::InsufficientStorage);
+        }
+        Ok(())
+    }
+
+    /// Writes the file, replacing the previous one at the path.
+    pub fn upload(&mut self, path: &str, data: Bytes) -> Result<(), NodeClientError> {
+        self.check_upload(path, data.len() as u64)?;
+        let now = Utc::now();
+        let created = self
+            .files
+            .get(&normalize(path))
+            .map_or(now, |file| file.created);
+        self.files.insert(
+            normalize(path),
+            FileNode {
+                data,
+                created,
+                last_modified: now,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn file(&self, path: &str) -> Result<&FileNode, NodeClientError> {
+        self.files
+            .get(&normalize(path))
+            .ok_or(NodeClientError::NotFound)
+    }
+
+    pub fn delete_file(&mut self, path: &str) -> Result<(), NodeClientError> {
+        self.files
+            .remove(&normalize(path))
+            .map(|_| ())
+            .ok_or(NodeClientError::NotFound)
+    }
+
+    pub fn rename_file(&mut self, from: &str, to: &str) -> Result<(), NodeClientError> {
+        let (from, to) = (normalize(from), normalize(to));
+        if !self.files.contains_key(&from) {
+            return Err(NodeClientError::NotFound);
+        }
+        if to.is_empty() || self.exists(&to) {
+            return Err(NodeClientError::EntityExists);
+        }
+        self.check_parent(&to)?;
+        let mut file = self.files.remove(&from).unwrap();
+        file.last_modified = Utc::now();
+        self.files.insert(to, file);
+        Ok(())
+    }
+
+    pub fn create_directory(&mut self, path: &str) -> Result<(), NodeClientError> {
+        let path = normalize(path);
+        if path.is_empty() || self.exists(&path) {
+            return Err(NodeClientError::EntityExists);
+        }
+        self.check_parent(&path)?;
+        let now = Utc::now();
+        self.directories.insert(
+            path,
+            DirectoryNode {
+                id: Uuid::new_v4(),
+                created: now,
+                last_modified: now,
+            },
+        );
+        Ok(())
+    }
+
+    /// Moves the directory along with everything inside of it.
+    pub fn rename_directory(&mut self, from: &str, to: &str) -> Result<(), NodeClientError> {
+        let (from, to) = (normalize(from), normalize(to));
+        if !self.directories.contains_key(&from) {
+            return Err(NodeClientError::NotFound);
+        }
+        if to.is_empty() || self.exists(&to) {
+            return Err(NodeClientError::EntityExists);
+        }
+        if is_inside(&to, &from) {
+            return Err(NodeClientError::BadRequest);
+        }
+        self.check_parent(&to)?;
+
+        let mut directory = self.directories.remove(&from).unwrap();
+        directory.last_modified = Utc::now();
+        self.directories.insert(to.clone(), directory);
+        self.directories = move_children(std::mem::take(&mut self.directories), &from, &to);
+        self.files = move_children(std::mem::take(&mut self.files), &from, &to);
+        Ok(())
+    }
+
+    pub fn delete_directory(&mut self, path: &str, recursive: bool) -> Result<(), NodeClientError> {
+        let path = normalize(path);
+        if !self.directories.contains_key(&path) {
+            return Err(NodeClientError::NotFound);
+        }
+        let empty = !self.files.keys().any(|file| is_inside(file, &path))
+            && !self.directories.keys().any(|dir| is_inside(dir, &path));
+        if !empty && !recursive {
+            return Err(NodeClientError::NotEmpty);
+        }
+        self.directories
+            .retain(|dir, _| *dir != path && !is_inside(dir, &path));
+        self.files.retain(|file, _| !is_inside(file, &path));
+        Ok(())
+    }
+
+    /// The files and directories directly inside the directory, sorted by name.
+    pub fn list_directory(&self, path: &str) -> Result<Vec<Entity>, NodeClientError> {
+        let path = normalize(path);
+        if !path.is_empty() && !self.directories.contains_key(&path) {
+            return Err(NodeClientError::NotFound);
+        }
+        let mut entities: Vec<Entity> = self
+            .directories
+            .iter()
+            .filter(|(dir, _)| parent(dir) == path)
+            .map(|(dir, node)| self.directory_entity(dir, node, basename(dir)))
+            .chain(
+                self.files
+                    .iter()
+                    .filter(|(file, _)| parent(file) == path)
+                    .map(|(file, node)| self.file_entity(file, node, basename(file))),
+            )
+            .collect();
+        entities.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(entities)
+    }
+
+    /// All files of the bucket, named by their full path.
+    #[cfg_attr(not(feature = "testing"), allow(dead_code))]
+    pub fn list_files(&self) -> Vec<Entity> {
+        self.files
+            .iter()
+            .map(|(file, node)| self.file_entity(file, node, file))
+            .collect()
+    }
+
+    /// All directories of the bucket, named by their full path.
+    #[cfg_attr(not(feature = "testing"), allow(dead_code))]
+    pub fn list_directories(&self) -> Vec<Entity> {
+        self.directories
+            .iter()
+            .map(|(dir, node)| self.directory_entity(dir, node, dir))
+            .collect()
+    }
+
+    pub fn stat(&self, path: &str) -> Result<Entity, NodeClientError> {
+        let path = normalize(path);
+        if let Some(node) = self.files.get(&path) {
+            return Ok(self.file_entity(&path, node, basename(&path)));
+        }
+        if let Some(node) = self.directories.get(&path) {
+            return Ok(self.directory_entity(&path, node, basename(&path)));
+        }
+        Err(NodeClientError::NotFound)
+    }
+
+    fn exists(&self, path: &str) -> bool {
+        self.files.contains_key(path) || self.directories.contains_key(path)
+    }
+
+    fn check_parent(&self, path: &str) -> Result<(), NodeClientError> {
+        let parent = parent(path);
+        if parent.is_empty() || self.directories.contains_key(parent) {
+            Ok(())
+        } else {
+            Err(NodeClientError::NotFound)
+        }
+    }
+
+    fn directory_id(&self, path: &str) -> Option<Uuid> {
+        self.directories.get(path).map(|dir| dir.id)
+    }
+
+    fn file_entity(&self, path: &str, node: &FileNode, name: &str) -> Entity {
+        Entity {
+            name: name.to_string(),
+            dir: self.directory_id(parent(path)),
+            dir_id: None,
+            size: node.data.len() as u64,
+            is_dir: false,
+            created: node.created,
+            last_modified: node.last_modified,
+        }
+    }
+
+    fn directory_entity(&self, path: &str, node: &DirectoryNode, name: &str) -> Entity {
+        Entity {
+            name: name.to_string(),
+            dir: self.directory_id(parent(path)),
+            dir_id: Some(node.id),
+            size: 0,
+            is_dir: true,
+            created: node.created,
+            last_modified: node.last_modified,
+        }
+    }
+}
+
+pub(crate) fn normalize(path: &str) -> String {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+fn parent(path: &str) -> &str {
+    path.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+fn basename(path: &str) -> &str {
+    path.rsplit_once('/').map_or(path, |(_, name)| name)
+}
+
+/// Whether `path` is a descendant of the directory.
+fn is_inside(path: &str, dir: &str) -> bool {
+    path.strip_prefix(dir)
+        .is_some_and(|rest| rest.starts_with('/'))
+}
+
+fn move_children<T>(entries: BTreeMap<String, T>, from: &str, to: &str) -> BTreeMap<String, T> {
+    entries
+        .into_iter()
+        .map(|(path, entry)| match is_inside(&path, from) {
+            true => (format!("{}{}", to, &path[from.len()..]), entry),
+            false => (path, entry),
+        })
+        .collect()
+}
@@ -0,0 +1,272 @@
+//! An in-process mock of a Meowith node, for hermetic tests of code using [`MeowithConnector`].
+//!
+//! The node keeps its buckets in memory and implements the file, directory, bucket and durable
+//! upload routes used by the connector, answering with the same error codes as a real node.
+//!
+//! ```no_run
+//! # async fn example() -> std::io::Result<()> {
+//! use meowith_connector::testing::{MockBucket, MockNode};
+//!
+//! let node = MockNode::start().await?;
+//! let bucket = MockBucket::new("media").quota(1024);
+//! node.add_bucket(&bucket);
+//!
+//! let connector = node.connector(&bucket);
+//! connector.create_directory("photos").await.unwrap();
+//! # Ok(())
+//! # }
+//! ```
+
+mod routes;
+
+use crate::connector::connector::MeowithConnector;
+use crate::error::NodeClientError;
+use crate::storage::tree::BucketTree;
+use bytes::{Bytes, BytesMut};
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::io;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::net::TcpListener;
+use tokio::sync::oneshot;
+use uuid::Uuid;
+
+/// Validity of upload sessions started on a [`MockNode`], unless changed.
+pub const DEFAULT_SESSION_VALIDITY: Duration = Duration::from_secs(60);
+
+/// The settings of a bucket hosted by a [`MockNode`].
+#[derive(Clone, Debug)]
+pub struct MockBucket {
+    pub app_id: Uuid,
+    pub bucket_id: Uuid,
+    pub name: String,
+    /// Storage quota in bytes
+    pub quota: i64,
+    pub encrypted: bool,
+    pub atomic_upload: bool,
+}
+
+impl MockBucket {
+    /// A bucket of a new app, without a quota.
+    pub fn new(name: &str) -> Self {
+        Self {
+            app_id: Uuid::new_v4(),
+            bucket_id: Uuid::new_v4(),
+            name: name.to_string(),
+            quota: i64::MAX,
+            encrypted: false,
+            atomic_upload: false,
+        }
+    }
+
+    pub fn quota(mut self, quota: i64) -> Self {
+        self.quota = quota;
+        self
+    }
+}
+
+struct HostedBucket {
+    settings: MockBucket,
+    created: DateTime<Utc>,
+    tree: BucketTree,
+}
+
+struct UploadSession {
+    app_id: Uuid,
+    bucket_id: Uuid,
+    path: String,
+    size: u64,
+    data: BytesMut,
+    expires: Instant,
+}
+
+struct NodeState {
+    buckets: HashMap<(Uuid, Uuid), HostedBucket>,
+    sessions: HashMap<Uuid, UploadSession>,
+    sessions_started: usize,
+    /// Byte limits of the next session puts, each failing once it's reached.
+    put_failures: VecDeque<u64>,
+    session_validity: Duration,
+    /// Delay before every request is handled.
+    latency: Duration,
+    in_flight: usize,
+    peak_in_flight: usize,
+}
+
+impl NodeState {
+    fn bucket(&self, app_id: Uuid, bucket_id: Uuid) -> Result<&HostedBucket, NodeClientError> {
+        self.buckets
+            .get(&(app_id, bucket_id))
+            .ok_or(NodeClientError::NotFound)
+    }
+
+    fn bucket_mut(
+        &mut self,
+        app_id: Uuid,
+        bucket_id: Uuid,
+    ) -> Result<&mut HostedBucket, NodeClientError> {
+        self.buckets
+            .get_mut(&(app_id, bucket_id))
+            .ok_or(NodeClientError::NotFound)
+    }
+
+    /// The session, unless it doesn't exist or has expired.
+    fn session(&mut self, code: Uuid) -> Result<&mut UploadSession, NodeClientError> {
+        let now = Instant::now();
+        self.sessions.retain(|_, session| session.expires > now);
+        self.sessions
+            .get_mut(&code)
+            .ok_or(NodeClientError::NoSuchSession)
+    }
+}
+
+type SharedState = Arc<Mutex<NodeState>>;
+
+/// A mock node listening on a random local port, stopped when dropped.
+///
+/// It accepts any bearer token and starts without any buckets.
+pub struct MockNode {
+    addr: String,
+    state: SharedState,
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl MockNode {
+    /// Starts the node on the current tokio runtime.
+    pub async fn start() -> io::Result<Self> {
+        let state = Arc::new(Mutex::new(NodeState {
+            buckets: HashMap::new(),
+            sessions: HashMap::new(),
+            sessions_started: 0,
+            put_failures: VecDeque::new(),
+            session_validity: DEFAULT_SESSION_VALIDITY,
+            latency: Duration::ZERO,
+            in_flight: 0,
+            peak_in_flight: 0,
+        }));
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let addr = format!("http://{}", listener.local_addr()?);
+        let (shutdown, stopped) = oneshot::channel();
+        let router = routes::router(state.clone());
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, router)
+                .with_graceful_shutdown(async {
+                    let _ = stopped.await;
+                })
+                .await;
+        });
+
+        Ok(Self {
+            addr,
+            state,
+            shutdown: Some(shutdown),
+        })
+    }
+
+    /// The base url of the node, like `http://127.0.0.1:41234`.
+    pub fn addr(&self) -> &str {
+        &self.addr
+    }
+
+    /// Hosts an empty bucket, replacing any previous bucket with the same ids.
+    pub fn add_bucket(&self, bucket: &MockBucket) {
+        self.state.lock().unwrap().buckets.insert(
+            (bucket.app_id, bucket.bucket_id),
+            HostedBucket {
+                settings: bucket.clone(),
+                created: Utc::now(),
+                tree: BucketTree::new(bucket.quota),
+            },
+        );
+    }
+
+    /// A connector for the bucket, using the default retry policy.
+    pub fn connector(&self, bucket: &MockBucket) -> MeowithConnector {
+        MeowithConnector::new(
+            "mock-token",
+            bucket.bucket_id,
+            bucket.app_id,
+            self.addr.clone(),
+        )
+    }
+
+    /// How long upload sessions stay valid without being resumed.
+    pub fn set_session_validity(&self, validity: Duration) {
+        self.state.lock().unwrap().session_validity = validity;
+    }
+
+    /// Delays the handling of every request, so that concurrent requests overlap.
+    pub fn set_latency(&self, latency: Duration) {
+        self.state.lock().unwrap().latency = latency;
+    }
+
+    /// The largest number of requests the node has been handling at once.
+    pub fn peak_concurrent_requests(&self) -> usize {
+        self.state.lock().unwrap().peak_in_flight
+    }
+
+    /// Writes a file directly, the parent directory must exist.
+    pub fn insert_file(
+        &self,
+        bucket: &MockBucket,
+        path: &str,
+        data: impl Into<Bytes>,
+    ) -> Result<(), NodeClientError> {
+        self.state
+            .lock()
+            .unwrap()
+            .bucket_mut(bucket.app_id, bucket.bucket_id)?
+            .tree
+            .upload(path, data.into())
+    }
+
+    pub fn insert_directory(&self, bucket: &MockBucket, path: &str) -> Result<(), NodeClientError> {
+        self.state
+            .lock()
+            .unwrap()
+            .bucket_mut(bucket.app_id, bucket.bucket_id)?
+            .tree
+            .create_directory(path)
+    }
+
+    /// The content of the file, if it exists.
+    pub fn file(&self, bucket: &MockBucket, path: &str) -> Option<Bytes> {
+        let state = self.state.lock().unwrap();
+        let file = state
+            .bucket(bucket.app_id, bucket.bucket_id)
+            .ok()?
+            .tree
+            .file(path)
+            .ok()?;
+        Some(file.data.clone())
+    }
+
+    /// Makes the next upload session put store only the first `bytes` of its body and fail with
+    /// `InternalError`, as if the connection dropped. Calls queue up for the following puts.
+    pub fn fail_put_after(&self, bytes: u64) {
+        self.state.lock().unwrap().put_failures.push_back(bytes);
+    }
+
+    /// Number of upload sessions started since the node started, telling durable uploads apart
+    /// from oneshot ones.
+    pub fn sessions_started(&self) -> usize {
+        self.state.lock().unwrap().sessions_started
+    }
+
+    /// Number of upload sessions which have not completed or expired.
+    pub fn pending_sessions(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        state.sessions.retain(|_, session| session.expires > now);
+        state.sessions.len()
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+    }
+}
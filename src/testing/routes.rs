@@ -0,0 +1,512 @@
+use super::{SharedState, UploadSession};
+use crate::dto::request::{
+    DeleteDirectoryRequest, RenameEntityRequest, UploadSessionRequest, UploadSessionResumeRequest,
+};
+use crate::dto::response::{
+    BucketDto, Entity, EntityList, UploadSessionResumeResponse, UploadSessionStartResponse,
+};
+use crate::error::NodeClientError;
+use crate::storage::tree::normalize;
+use axum::body::{Body, Bytes};
+use axum::extract::{DefaultBodyLimit, Path, Query, Request, State};
+use axum::http::header::{AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_RANGE, CONTENT_TYPE, RANGE};
+use axum::http::{HeaderMap, StatusCode};
+use axum::middleware::{self, Next};
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post, put};
+use axum::{Json, Router};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use std::time::Instant;
+use uuid::Uuid;
+
+pub(super) fn router(state: SharedState) -> Router {
+    Router::new()
+        .route(
+            "/api/file/upload/oneshot/:app_id/:bucket_id/*path",
+            post(upload_oneshot),
+        )
+        .route(
+            "/api/file/delete/:app_id/:bucket_id/*path",
+            delete(delete_file),
+        )
+        .route(
+            "/api/file/rename/:app_id/:bucket_id/*path",
+            post(rename_file),
+        )
+        .route(
+            "/api/file/download/:app_id/:bucket_id/*path",
+            get(download_file),
+        )
+        .route(
+            "/api/file/upload/durable/:app_id/:bucket_id/*path",
+            post(start_upload_session),
+        )
+        .route(
+            "/api/file/upload/resume/:app_id/:bucket_id",
+            post(resume_upload_session),
+        )
+        .route(
+            "/api/file/upload/put/:app_id/:bucket_id/:session_id",
+            put(put_file),
+        )
+        .route(
+            "/api/directory/create/:app_id/:bucket_id/*path",
+            post(create_directory),
+        )
+        .route(
+            "/api/directory/rename/:app_id/:bucket_id/*path",
+            post(rename_directory),
+        )
+        .route(
+            "/api/directory/delete/:app_id/:bucket_id/*path",
+            delete(delete_directory),
+        )
+        .route("/api/directory/list/:app_id/:bucket_id/", get(list_root))
+        .route(
+            "/api/directory/list/:app_id/:bucket_id/*path",
+            get(list_directory),
+        )
+        .route(
+            "/api/bucket/list/files/:app_id/:bucket_id",
+            get(list_bucket_files),
+        )
+        .route(
+            "/api/bucket/list/directories/:app_id/:bucket_id",
+            get(list_bucket_directories),
+        )
+        .route(
+            "/api/bucket/stat/:app_id/:bucket_id/*path",
+            get(stat_resource),
+        )
+        .route(
+            "/api/bucket/info/:app_id/:bucket_id",
+            get(fetch_bucket_info),
+        )
+        .layer(middleware::from_fn(authorize))
+        .layer(middleware::from_fn_with_state(state.clone(), track))
+        .layer(DefaultBodyLimit::disable())
+        .with_state(state)
+}
+
+/// An error code sent the way a real node does.
+struct NodeError(NodeClientError);
+
+impl From<NodeClientError> for NodeError {
+    fn from(value: NodeClientError) -> Self {
+        NodeError(value)
+    }
+}
+
+impl IntoResponse for NodeError {
+    fn into_response(self) -> Response {
+        let status = match self.0 {
+            NodeClientError::BadRequest => StatusCode::BAD_REQUEST,
+            NodeClientError::BadAuth => StatusCode::UNAUTHORIZED,
+            NodeClientError::NotFound | NodeClientError::NoSuchSession => StatusCode::NOT_FOUND,
+            NodeClientError::EntityExists | NodeClientError::NotEmpty => StatusCode::CONFLICT,
+            NodeClientError::InsufficientStorage => StatusCode::PAYLOAD_TOO_LARGE,
+            NodeClientError::RangeUnsatisfiable => StatusCode::RANGE_NOT_SATISFIABLE,
+            NodeClientError::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            NodeClientError::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+            NodeClientError::InternalError | NodeClientError::Unknown { .. } => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+        };
+        let body = serde_json::json!({ "code": self.0.to_string() });
+        (status, Json(body)).into_response()
+    }
+}
+
+type NodeResult<T> = Result<T, NodeError>;
+
+type EntityPath = Path<(Uuid, Uuid, String)>;
+
+/// Counts the request as in flight for as long as it's handled.
+struct InFlight(SharedState);
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        self.0.lock().unwrap().in_flight -= 1;
+    }
+}
+
+async fn track(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    let latency = {
+        let mut state = state.lock().unwrap();
+        state.in_flight += 1;
+        state.peak_in_flight = state.peak_in_flight.max(state.in_flight);
+        state.latency
+    };
+    let _in_flight = InFlight(state);
+    tokio::time::sleep(latency).await;
+    next.run(request).await
+}
+
+async fn authorize(request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .is_some_and(|header| header.starts_with("Bearer "));
+    if !authorized {
+        return NodeError(NodeClientError::BadAuth).into_response();
+    }
+    next.run(request).await
+}
+
+async fn upload_oneshot(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+    body: Bytes,
+) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    state
+        .bucket_mut(app_id, bucket_id)?
+        .tree
+        .upload(&path, body)?;
+    Ok(())
+}
+
+async fn delete_file(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    state
+        .bucket_mut(app_id, bucket_id)?
+        .tree
+        .delete_file(&path)?;
+    Ok(())
+}
+
+async fn rename_file(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+    Json(req): Json<RenameEntityRequest>,
+) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    state
+        .bucket_mut(app_id, bucket_id)?
+        .tree
+        .rename_file(&path, &req.to)?;
+    Ok(())
+}
+
+async fn download_file(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+    headers: HeaderMap,
+) -> NodeResult<Response> {
+    let data = {
+        let state = state.lock().unwrap();
+        state
+            .bucket(app_id, bucket_id)?
+            .tree
+            .file(&path)?
+            .data
+            .clone()
+    };
+    let length = data.len() as u64;
+    let path = normalize(&path);
+    let name = path
+        .rsplit_once('/')
+        .map_or(path.as_str(), |(_, name)| name);
+
+    let response = Response::builder()
+        .header("X-File-Content-Length", length)
+        .header(CONTENT_TYPE, "application/octet-stream")
+        .header(
+            CONTENT_DISPOSITION,
+            format!("attachment; filename*=UTF-8''{}", urlencoding::encode(name)),
+        );
+    let range = headers
+        .get(RANGE)
+        .map(|range| parse_range(range.to_str().unwrap_or_default(), length))
+        .transpose()?;
+    let response = match range {
+        Some((start, end)) => response
+            .status(StatusCode::PARTIAL_CONTENT)
+            .header(CONTENT_RANGE, format!("bytes {}-{}/{}", start, end, length))
+            .body(Body::from(data.slice(start as usize..=end as usize))),
+        None => response.body(Body::from(data)),
+    };
+    Ok(response.unwrap())
+}
+
+/// Resolves a single `bytes=` range into inclusive bounds within the file.
+fn parse_range(header: &str, length: u64) -> Result<(u64, u64), NodeClientError> {
+    let (start, end) = header
+        .strip_prefix("bytes=")
+        .and_then(|range| range.split_once('-'))
+        .ok_or(NodeClientError::BadRequest)?;
+    let parse = |bound: &str| {
+        bound
+            .trim()
+            .parse::<u64>()
+            .map_err(|_| NodeClientError::BadRequest)
+    };
+    let (start, end) = match (start.trim(), end.trim()) {
+        ("", "") => return Err(NodeClientError::BadRequest),
+        ("", suffix) => (
+            length.saturating_sub(parse(suffix)?),
+            length.saturating_sub(1),
+        ),
+        (start, "") => (parse(start)?, length.saturating_sub(1)),
+        (start, end) => (parse(start)?, parse(end)?.min(length.saturating_sub(1))),
+    };
+    if start >= length || start > end {
+        return Err(NodeClientError::RangeUnsatisfiable);
+    }
+    Ok((start, end))
+}
+
+async fn start_upload_session(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+    Json(req): Json<UploadSessionRequest>,
+) -> NodeResult<Json<UploadSessionStartResponse>> {
+    let mut state = state.lock().unwrap();
+    state
+        .bucket(app_id, bucket_id)?
+        .tree
+        .check_upload(&path, req.size)?;
+
+    let code = Uuid::new_v4();
+    let validity = state.session_validity;
+    state.sessions_started += 1;
+    state.sessions.insert(
+        code,
+        UploadSession {
+            app_id,
+            bucket_id,
+            path,
+            size: req.size,
+            data: Default::default(),
+            expires: Instant::now() + validity,
+        },
+    );
+    Ok(Json(UploadSessionStartResponse {
+        code: code.to_string(),
+        validity: validity.as_secs() as u32,
+        uploaded: 0,
+    }))
+}
+
+async fn resume_upload_session(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id)): Path<(Uuid, Uuid)>,
+    Json(req): Json<UploadSessionResumeRequest>,
+) -> NodeResult<Json<UploadSessionResumeResponse>> {
+    let mut state = state.lock().unwrap();
+    let validity = state.session_validity;
+    let session = state.session(req.session_id)?;
+    if (session.app_id, session.bucket_id) != (app_id, bucket_id) {
+        return Err(NodeClientError::NoSuchSession.into());
+    }
+    session.expires = Instant::now() + validity;
+    Ok(Json(UploadSessionResumeResponse {
+        uploaded_size: session.data.len() as u64,
+    }))
+}
+
+/// Appends the body to the session, keeping what was received when the connection drops.
+async fn put_file(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, code)): Path<(Uuid, Uuid, Uuid)>,
+    body: Body,
+) -> NodeResult<()> {
+    let mut limit = {
+        let mut state = state.lock().unwrap();
+        let session = state.session(code)?;
+        if (session.app_id, session.bucket_id) != (app_id, bucket_id) {
+            return Err(NodeClientError::NoSuchSession.into());
+        }
+        state.put_failures.pop_front()
+    };
+
+    let mut body = body.into_data_stream();
+    while let Some(chunk) = body.next().await {
+        let chunk = chunk.map_err(|_| NodeClientError::BadRequest)?;
+        let chunk = match &mut limit {
+            Some(remaining) => {
+                let take = chunk.len().min(*remaining as usize);
+                *remaining -= take as u64;
+                chunk.slice(..take)
+            }
+            None => chunk,
+        };
+        append_chunk(&state, code, &chunk)?;
+    }
+    if limit.is_some() {
+        return Err(NodeClientError::InternalError.into());
+    }
+    complete_session(&state, code)
+}
+
+fn append_chunk(state: &SharedState, code: Uuid, chunk: &[u8]) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    let validity = state.session_validity;
+    let session = state.session(code)?;
+    if session.data.len() + chunk.len() > session.size as usize {
+        return Err(NodeClientError::BadRequest.into());
+    }
+    session.data.extend_from_slice(chunk);
+    session.expires = Instant::now() + validity;
+    Ok(())
+}
+
+/// Moves the uploaded file into the bucket, unless parts of it are still missing.
+fn complete_session(state: &SharedState, code: Uuid) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    let session = state.session(code)?;
+    if (session.data.len() as u64) < session.size {
+        return Err(NodeClientError::BadRequest.into());
+    }
+    let session = state.sessions.remove(&code).unwrap();
+    state
+        .bucket_mut(session.app_id, session.bucket_id)?
+        .tree
+        .upload(&session.path, session.data.freeze())?;
+    Ok(())
+}
+
+async fn create_directory(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    state
+        .bucket_mut(app_id, bucket_id)?
+        .tree
+        .create_directory(&path)?;
+    Ok(())
+}
+
+async fn rename_directory(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+    Json(req): Json<RenameEntityRequest>,
+) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    state
+        .bucket_mut(app_id, bucket_id)?
+        .tree
+        .rename_directory(&path, &req.to)?;
+    Ok(())
+}
+
+async fn delete_directory(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+    Json(req): Json<DeleteDirectoryRequest>,
+) -> NodeResult<()> {
+    let mut state = state.lock().unwrap();
+    state
+        .bucket_mut(app_id, bucket_id)?
+        .tree
+        .delete_directory(&path, req.recursive)?;
+    Ok(())
+}
+
+/// The `start` and `end` query parameters of the listings, the end is exclusive.
+///
+/// An open-ended `start` may carry a trailing `-`, as the connector sends it. Anything else but
+/// plain numbers is rejected with `400 Bad Request`.
+#[derive(Deserialize)]
+struct Pagination {
+    start: Option<String>,
+    end: Option<String>,
+}
+
+impl Pagination {
+    fn page(&self, entities: Vec<Entity>) -> NodeResult<Json<EntityList>> {
+        let start = match &self.start {
+            Some(start) => Some(parse_bound(start.strip_suffix('-').unwrap_or(start))?),
+            None => None,
+        };
+        let end = self.end.as_deref().map(parse_bound).transpose()?;
+        let end = end.unwrap_or(entities.len()).min(entities.len());
+        let start = start.unwrap_or(0).min(end);
+        Ok(Json(EntityList {
+            entities: entities[start..end].to_vec(),
+        }))
+    }
+}
+
+fn parse_bound(bound: &str) -> Result<usize, NodeClientError> {
+    if !bound.bytes().all(|byte| byte.is_ascii_digit()) {
+        return Err(NodeClientError::BadRequest);
+    }
+    bound.parse().map_err(|_| NodeClientError::BadRequest)
+}
+
+async fn list_root(
+    state: State<SharedState>,
+    Path((app_id, bucket_id)): Path<(Uuid, Uuid)>,
+    pagination: Query<Pagination>,
+) -> NodeResult<Json<EntityList>> {
+    list_directory(state, Path((app_id, bucket_id, String::new())), pagination).await
+}
+
+async fn list_directory(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+    Query(pagination): Query<Pagination>,
+) -> NodeResult<Json<EntityList>> {
+    let state = state.lock().unwrap();
+    let entities = state
+        .bucket(app_id, bucket_id)?
+        .tree
+        .list_directory(&path)?;
+    pagination.page(entities)
+}
+
+async fn list_bucket_files(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id)): Path<(Uuid, Uuid)>,
+    Query(pagination): Query<Pagination>,
+) -> NodeResult<Json<EntityList>> {
+    let state = state.lock().unwrap();
+    let entities = state.bucket(app_id, bucket_id)?.tree.list_files();
+    pagination.page(entities)
+}
+
+async fn list_bucket_directories(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id)): Path<(Uuid, Uuid)>,
+    Query(pagination): Query<Pagination>,
+) -> NodeResult<Json<EntityList>> {
+    let state = state.lock().unwrap();
+    let entities = state.bucket(app_id, bucket_id)?.tree.list_directories();
+    pagination.page(entities)
+}
+
+async fn stat_resource(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id, path)): EntityPath,
+) -> NodeResult<Json<Entity>> {
+    let state = state.lock().unwrap();
+    let entity = state.bucket(app_id, bucket_id)?.tree.stat(&path)?;
+    Ok(Json(entity))
+}
+
+async fn fetch_bucket_info(
+    State(state): State<SharedState>,
+    Path((app_id, bucket_id)): Path<(Uuid, Uuid)>,
+) -> NodeResult<Json<BucketDto>> {
+    let state = state.lock().unwrap();
+    let bucket = state.bucket(app_id, bucket_id)?;
+    Ok(Json(BucketDto {
+        app_id,
+        id: bucket_id,
+        name: bucket.settings.name.clone(),
+        encrypted: bucket.settings.encrypted,
+        atomic_upload: bucket.settings.atomic_upload,
+        quota: bucket.settings.quota,
+        file_count: bucket.tree.file_count(),
+        space_taken: bucket.tree.space_taken(),
+        created: bucket.created,
+        last_modified: bucket.created,
+    }))
+}
@@ -0,0 +1,344 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::RecordingNode;
+use meowith_connector::connector::admin::MeowithAdminConnector;
+use meowith_connector::dto::permission::BucketPermissions;
+use meowith_connector::dto::request::{
+    AppRolePath, CreateBucketRequest, MemberIdRequest, ScopedPermission,
+};
+use serde_json::json;
+use uuid::Uuid;
+
+const APP: Uuid = Uuid::from_u128(0xa);
+const BUCKET: Uuid = Uuid::from_u128(0xb);
+const USER: Uuid = Uuid::from_u128(0xc);
+
+fn connector(node: &RecordingNode) -> MeowithAdminConnector {
+    MeowithAdminConnector::new("token", node.addr.clone())
+}
+
+fn app() -> serde_json::Value {
+    json!({
+        "id": APP,
+        "name": "app",
+        "quota": 1024,
+        "created": "2024-01-01T00:00:00Z",
+        "last_modified": "2024-01-02T00:00:00Z",
+    })
+}
+
+fn bucket() -> serde_json::Value {
+    json!({
+        "app_id": APP,
+        "id": BUCKET,
+        "name": "bucket",
+        "encrypted": true,
+        "atomic_upload": false,
+        "quota": 2048,
+        "file_count": 0,
+        "space_taken": 0,
+        "created": "2024-01-01T00:00:00Z",
+        "last_modified": "2024-01-02T00:00:00Z",
+    })
+}
+
+fn role() -> AppRolePath {
+    AppRolePath {
+        name: "read only".to_string(),
+        app_id: APP,
+    }
+}
+
+fn member() -> MemberIdRequest {
+    MemberIdRequest {
+        app_id: APP,
+        id: USER,
+    }
+}
+
+#[tokio::test]
+async fn create_app() {
+    let node = RecordingNode::json(app()).await;
+    let app = connector(&node).create_app("app").await.unwrap();
+    assert_eq!(app.quota, 1024);
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, "/api/app/create");
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+    assert_eq!(request.json(), json!({ "name": "app" }));
+}
+
+#[tokio::test]
+async fn list_apps() {
+    let node = RecordingNode::json(json!({ "apps": [app()] })).await;
+    let apps = connector(&node).list_apps().await.unwrap();
+    assert_eq!(apps.apps.len(), 1);
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.path, "/api/app/owned");
+}
+
+#[tokio::test]
+async fn fetch_app() {
+    let node = RecordingNode::json(app()).await;
+    connector(&node).fetch_app(APP).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.path, format!("/api/app/info/{APP}"));
+}
+
+#[tokio::test]
+async fn rename_app() {
+    let node = RecordingNode::json(app()).await;
+    connector(&node).rename_app(APP, "renamed").await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, format!("/api/app/rename/{APP}"));
+    assert_eq!(request.json(), json!({ "to": "renamed" }));
+}
+
+#[tokio::test]
+async fn delete_app() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).delete_app(APP).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(request.path, format!("/api/app/delete/{APP}"));
+}
+
+#[tokio::test]
+async fn issue_token() {
+    let node = RecordingNode::json(json!({ "token": "secret" })).await;
+    let perms = vec![ScopedPermission::builder(BUCKET).read().list().build()];
+    let issued = connector(&node)
+        .issue_token(APP, "ci", perms)
+        .await
+        .unwrap();
+    assert_eq!(issued.token, "secret");
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, "/api/token/issue");
+    let allowance = (BucketPermissions::READ | BucketPermissions::LIST).allowance();
+    assert_eq!(
+        request.json(),
+        json!({
+            "app_id": APP,
+            "name": "ci",
+            "perms": [{ "bucket_id": BUCKET, "allowance": allowance }],
+        })
+    );
+}
+
+#[tokio::test]
+async fn list_tokens() {
+    let node = RecordingNode::json(json!({ "tokens": [] })).await;
+    connector(&node).list_tokens(APP, Some(USER)).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, "/api/token/list");
+    assert_eq!(request.json(), json!({ "app_id": APP, "issuer": USER }));
+}
+
+#[tokio::test]
+async fn delete_token() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node)
+        .delete_token(APP, USER, "ci")
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(request.path, "/api/token/delete");
+    assert_eq!(
+        request.json(),
+        json!({ "app_id": APP, "issuer_id": USER, "name": "ci" })
+    );
+}
+
+#[tokio::test]
+async fn create_role() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).create_role(&role()).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, format!("/api/role/{APP}/read%20only"));
+    assert!(request.body.is_empty());
+}
+
+#[tokio::test]
+async fn modify_role() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    let perms = vec![ScopedPermission::new(BUCKET, BucketPermissions::ADMIN)];
+    connector(&node).modify_role(&role(), perms).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::PATCH);
+    assert_eq!(request.path, format!("/api/role/{APP}/read%20only"));
+    assert_eq!(
+        request.json(),
+        json!({
+            "perms": [{
+                "bucket_id": BUCKET,
+                "allowance": BucketPermissions::ADMIN.allowance(),
+            }],
+        })
+    );
+}
+
+#[tokio::test]
+async fn delete_role() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).delete_role(&role()).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(request.path, format!("/api/role/{APP}/read%20only"));
+}
+
+#[tokio::test]
+async fn list_roles() {
+    let node = RecordingNode::json(json!({ "roles": [] })).await;
+    connector(&node).list_roles(APP).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.path, format!("/api/role/{APP}"));
+}
+
+#[tokio::test]
+async fn add_member() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).add_member(APP, USER).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, "/api/app/member/add");
+    assert_eq!(request.json(), json!({ "app_id": APP, "member_id": USER }));
+}
+
+#[tokio::test]
+async fn set_member_roles() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node)
+        .set_member_roles(&member(), vec!["read only".to_string()])
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::PATCH);
+    assert_eq!(request.path, format!("/api/app/member/{APP}/{USER}"));
+    assert_eq!(request.json(), json!({ "roles": ["read only"] }));
+}
+
+#[tokio::test]
+async fn remove_member() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).remove_member(&member()).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(request.path, format!("/api/app/member/{APP}/{USER}"));
+}
+
+#[tokio::test]
+async fn list_members() {
+    let node = RecordingNode::json(json!({ "members": [] })).await;
+    connector(&node).list_members(APP).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.path, format!("/api/app/member/{APP}"));
+}
+
+#[tokio::test]
+async fn create_bucket() {
+    let node = RecordingNode::json(bucket()).await;
+    let req = CreateBucketRequest {
+        name: "bucket".to_string(),
+        quota: 2048,
+        encrypted: true,
+        atomic_upload: false,
+    };
+    let bucket = connector(&node).create_bucket(APP, &req).await.unwrap();
+    assert!(bucket.encrypted);
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, format!("/api/bucket/create/{APP}"));
+    assert_eq!(
+        request.json(),
+        json!({
+            "name": "bucket",
+            "quota": 2048,
+            "encrypted": true,
+            "atomic_upload": false,
+        })
+    );
+}
+
+#[tokio::test]
+async fn list_buckets() {
+    let node = RecordingNode::json(json!({ "buckets": [bucket()] })).await;
+    let buckets = connector(&node).list_buckets(APP).await.unwrap();
+    assert_eq!(buckets.buckets[0].id, BUCKET);
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.path, format!("/api/bucket/list/{APP}"));
+}
+
+#[tokio::test]
+async fn set_bucket_quota() {
+    let node = RecordingNode::json(bucket()).await;
+    connector(&node)
+        .set_bucket_quota(APP, BUCKET, 4096)
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(request.path, format!("/api/bucket/quota/{APP}/{BUCKET}"));
+    assert_eq!(request.json(), json!({ "quota": 4096 }));
+}
+
+#[tokio::test]
+async fn delete_bucket() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).delete_bucket(APP, BUCKET).await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(request.path, format!("/api/bucket/delete/{APP}/{BUCKET}"));
+}
+
+#[tokio::test]
+async fn builder_rejects_invalid_token() {
+    let result =
+        MeowithAdminConnector::builder("bad\ntoken", "http://127.0.0.1:1".to_string()).build();
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn builder_configures_client() {
+    let node = RecordingNode::json(json!({ "apps": [] })).await;
+    let connector = MeowithAdminConnector::builder("token", node.addr.clone())
+        .user_agent("admin-test")
+        .timeout(std::time::Duration::from_secs(5))
+        .build()
+        .unwrap();
+    connector.list_apps().await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.header("user-agent"), Some("admin-test"));
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+}
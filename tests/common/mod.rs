@@ -0,0 +1,115 @@
+// Each test crate only uses part of the helpers.
+#![allow(dead_code)]
+
+use axum::body::{to_bytes, Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{HeaderMap, Method, StatusCode};
+use axum::response::Response;
+use axum::Router;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use tokio::net::TcpListener;
+
+/// A request received by the [`RecordingNode`].
+#[derive(Debug, Clone)]
+pub struct Recorded {
+    pub method: Method,
+    pub path: String,
+    pub query: Option<String>,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+}
+
+impl Recorded {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    pub fn json(&self) -> Value {
+        serde_json::from_slice(&self.body).expect("the body is not json")
+    }
+}
+
+struct Reply {
+    status: StatusCode,
+    headers: Vec<(&'static str, String)>,
+    body: Bytes,
+}
+
+#[derive(Clone)]
+struct MockState {
+    reply: Arc<Reply>,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+/// Answers every request with the same canned response, recording what it received.
+pub struct RecordingNode {
+    pub addr: String,
+    requests: Arc<Mutex<Vec<Recorded>>>,
+}
+
+impl RecordingNode {
+    pub async fn start(status: StatusCode, body: impl Into<Bytes>) -> Self {
+        Self::start_with_headers(status, Vec::new(), body).await
+    }
+
+    pub async fn json(body: Value) -> Self {
+        Self::start_with_headers(
+            StatusCode::OK,
+            vec![("content-type", "application/json".to_string())],
+            body.to_string(),
+        )
+        .await
+    }
+
+    pub async fn start_with_headers(
+        status: StatusCode,
+        headers: Vec<(&'static str, String)>,
+        body: impl Into<Bytes>,
+    ) -> Self {
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let state = MockState {
+            reply: Arc::new(Reply {
+                status,
+                headers,
+                body: body.into(),
+            }),
+            requests: requests.clone(),
+        };
+        let router = Router::new().fallback(record).with_state(state);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        Self { addr, requests }
+    }
+
+    /// Every request received so far, in order.
+    pub fn requests(&self) -> Vec<Recorded> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// The only request received so far.
+    pub fn request(&self) -> Recorded {
+        let requests = self.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1, "expected exactly one request");
+        requests[0].clone()
+    }
+}
+
+async fn record(State(state): State<MockState>, request: Request) -> Response {
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, usize::MAX).await.unwrap();
+    state.requests.lock().unwrap().push(Recorded {
+        method: parts.method,
+        path: parts.uri.path().to_string(),
+        query: parts.uri.query().map(str::to_string),
+        headers: parts.headers,
+        body,
+    });
+
+    let mut response = Response::builder().status(state.reply.status);
+    for (name, value) in &state.reply.headers {
+        response = response.header(*name, value);
+    }
+    response.body(Body::from(state.reply.body.clone())).unwrap()
+}
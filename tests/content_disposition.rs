@@ -0,0 +1,102 @@
+use meowith_connector::connector::headers::{extract_filename, path_basename, ContentDisposition};
+
+#[test]
+fn plain_filename() {
+    assert_eq!(
+        extract_filename("attachment; filename=report.csv"),
+        Some("report.csv".to_string())
+    );
+    assert_eq!(
+        extract_filename("attachment; filename=\"report 2024.csv\""),
+        Some("report 2024.csv".to_string())
+    );
+}
+
+#[test]
+fn parameters_after_the_filename() {
+    assert_eq!(
+        extract_filename("attachment; filename=\"a.txt\"; size=12"),
+        Some("a.txt".to_string())
+    );
+    assert_eq!(
+        extract_filename("attachment; filename=a.txt; creation-date=\"Wed, 12 Feb 1997\""),
+        Some("a.txt".to_string())
+    );
+}
+
+#[test]
+fn escaped_quotes() {
+    assert_eq!(
+        extract_filename(r#"attachment; filename="say \"meow\".txt""#),
+        Some("say \"meow\".txt".to_string())
+    );
+    assert_eq!(
+        extract_filename(r#"attachment; filename="back\\slash;semi.txt""#),
+        Some("back\\slash;semi.txt".to_string())
+    );
+}
+
+#[test]
+fn extended_filename_is_percent_decoded() {
+    assert_eq!(
+        extract_filename("attachment; filename*=UTF-8''%E2%82%AC%20rates.txt"),
+        Some("€ rates.txt".to_string())
+    );
+    assert_eq!(
+        extract_filename("attachment; filename*=utf-8'en'na%C3%AFve.txt"),
+        Some("naïve.txt".to_string())
+    );
+    assert_eq!(
+        extract_filename("attachment; filename*=ISO-8859-1''caf%E9.txt"),
+        Some("café.txt".to_string())
+    );
+}
+
+#[test]
+fn extended_filename_is_preferred_in_any_order() {
+    let expected = Some("€.txt".to_string());
+    assert_eq!(
+        extract_filename("attachment; filename=\"EUR.txt\"; filename*=UTF-8''%E2%82%AC.txt"),
+        expected
+    );
+    assert_eq!(
+        extract_filename("attachment; filename*=UTF-8''%E2%82%AC.txt; filename=\"EUR.txt\""),
+        expected
+    );
+}
+
+#[test]
+fn undecodable_extended_filename_falls_back() {
+    assert_eq!(
+        extract_filename("attachment; filename*=UTF-8''%FF%FE.txt; filename=plain.txt"),
+        Some("plain.txt".to_string())
+    );
+    assert_eq!(
+        extract_filename("attachment; filename*=KOI8-R''%C1.txt; filename=plain.txt"),
+        Some("plain.txt".to_string())
+    );
+    assert_eq!(extract_filename("attachment; filename*=garbage"), None);
+}
+
+#[test]
+fn case_and_whitespace_are_tolerated() {
+    let parsed = ContentDisposition::parse("  INLINE ;FILENAME = \"a.txt\" ").unwrap();
+    assert_eq!(parsed.disposition, "inline");
+    assert_eq!(parsed.filename, Some("a.txt".to_string()));
+}
+
+#[test]
+fn missing_filename() {
+    assert_eq!(extract_filename("attachment"), None);
+    assert_eq!(extract_filename("inline; size=10"), None);
+    assert_eq!(extract_filename("attachment; filename=\"\""), None);
+    assert_eq!(extract_filename(""), None);
+    assert_eq!(ContentDisposition::parse(";filename=a"), None);
+}
+
+#[test]
+fn basename_fallback() {
+    assert_eq!(path_basename("dir/sub/file.txt"), "file.txt");
+    assert_eq!(path_basename("file.txt"), "file.txt");
+    assert_eq!(path_basename("dir/sub/"), "sub");
+}
@@ -0,0 +1,42 @@
+use meowith_connector::dto::range::ContentRange;
+
+#[test]
+fn parses_ranges() {
+    let range = ContentRange::parse("bytes 0-99/1234").unwrap();
+    assert_eq!((range.start, range.end, range.total), (0, 99, Some(1234)));
+    assert_eq!(range.length(), 100);
+
+    let range = ContentRange::parse("bytes 5-5/*").unwrap();
+    assert_eq!((range.start, range.end, range.total), (5, 5, None));
+    assert_eq!(range.length(), 1);
+}
+
+#[test]
+fn rejects_invalid_ranges() {
+    for header in [
+        "bytes 5-3/10",
+        "bytes 5-10/10",
+        "bytes 0-0/0",
+        "bytes */10",
+        "bytes 0-x/10",
+        "items 0-1/10",
+    ] {
+        assert_eq!(ContentRange::parse(header), None, "{}", header);
+    }
+}
+
+#[test]
+fn length_never_underflows() {
+    let range = ContentRange {
+        start: 5,
+        end: 3,
+        total: None,
+    };
+    assert_eq!(range.length(), 0);
+    let range = ContentRange {
+        start: 0,
+        end: u64::MAX,
+        total: None,
+    };
+    assert_eq!(range.length(), u64::MAX);
+}
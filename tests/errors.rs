@@ -0,0 +1,171 @@
+use meowith_connector::connector::retry::RetryPolicy;
+use meowith_connector::error::{ConnectorError, NodeClientError};
+use reqwest::StatusCode;
+use std::error::Error;
+
+#[test]
+fn known_codes_win_over_the_status() {
+    assert_eq!(
+        NodeClientError::decode(StatusCode::BAD_REQUEST, r#"{"code":"NotEmpty"}"#),
+        NodeClientError::NotEmpty
+    );
+    assert_eq!(
+        NodeClientError::decode(StatusCode::INTERNAL_SERVER_ERROR, r#"{"code":"NotFound"}"#),
+        NodeClientError::NotFound
+    );
+}
+
+#[test]
+fn unknown_codes_keep_the_response() {
+    let body = r#"{"code":"QuotaFrozen"}"#;
+    let error = NodeClientError::decode(StatusCode::FORBIDDEN, body);
+    assert_eq!(
+        error,
+        NodeClientError::Unknown {
+            code: Some("QuotaFrozen".to_string()),
+            status: Some(StatusCode::FORBIDDEN),
+            body: Some(body.to_string()),
+        }
+    );
+    assert_eq!(error.to_string(), "Unknown QuotaFrozen (403 Forbidden)");
+    assert_eq!(error.known(), None);
+}
+
+#[test]
+fn bare_statuses() {
+    for (status, expected) in [
+        (StatusCode::BAD_REQUEST, NodeClientError::BadRequest),
+        (StatusCode::UNAUTHORIZED, NodeClientError::BadAuth),
+        (StatusCode::NOT_FOUND, NodeClientError::NotFound),
+        (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            NodeClientError::InsufficientStorage,
+        ),
+        (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            NodeClientError::RangeUnsatisfiable,
+        ),
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            NodeClientError::TooManyRequests,
+        ),
+        (StatusCode::BAD_GATEWAY, NodeClientError::Unavailable),
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            NodeClientError::Unavailable,
+        ),
+    ] {
+        assert_eq!(NodeClientError::decode(status, ""), expected, "{}", status);
+        assert_eq!(
+            NodeClientError::decode(status, "<html>nginx</html>"),
+            expected,
+            "{}",
+            status
+        );
+        assert_eq!(
+            NodeClientError::decode(status, r#"{"message":"no code"}"#),
+            expected,
+            "{}",
+            status
+        );
+    }
+}
+
+#[test]
+fn unmapped_statuses_are_unknown() {
+    assert_eq!(
+        NodeClientError::decode(StatusCode::IM_A_TEAPOT, "short and stout"),
+        NodeClientError::Unknown {
+            code: None,
+            status: Some(StatusCode::IM_A_TEAPOT),
+            body: Some("short and stout".to_string()),
+        }
+    );
+    assert_eq!(
+        NodeClientError::decode(StatusCode::IM_A_TEAPOT, ""),
+        NodeClientError::Unknown {
+            code: None,
+            status: Some(StatusCode::IM_A_TEAPOT),
+            body: None,
+        }
+    );
+}
+
+fn unknown(status: StatusCode) -> NodeClientError {
+    NodeClientError::Unknown {
+        code: Some("Future".to_string()),
+        status: Some(status),
+        body: None,
+    }
+}
+
+#[test]
+fn retryable_and_not_found() {
+    assert!(NodeClientError::InternalError.is_retryable());
+    assert!(NodeClientError::TooManyRequests.is_retryable());
+    assert!(NodeClientError::Unavailable.is_retryable());
+    assert!(!NodeClientError::NotFound.is_retryable());
+    assert!(unknown(StatusCode::BAD_GATEWAY).is_retryable());
+    assert!(unknown(StatusCode::TOO_MANY_REQUESTS).is_retryable());
+    assert!(unknown(StatusCode::NOT_IMPLEMENTED).is_retryable());
+    assert!(!unknown(StatusCode::CONFLICT).is_retryable());
+
+    assert!(NodeClientError::NotFound.is_not_found());
+    assert!(NodeClientError::NoSuchSession.is_not_found());
+    assert!(unknown(StatusCode::NOT_FOUND).is_not_found());
+    assert!(!unknown(StatusCode::GONE).is_not_found());
+    assert!(!NodeClientError::BadRequest.is_not_found());
+}
+
+#[test]
+fn unknown_errors_follow_the_configured_retry_list() {
+    let retry = RetryPolicy::default();
+    let error = |status| ConnectorError::remote(unknown(status));
+    assert!(retry.is_retryable(&error(StatusCode::SERVICE_UNAVAILABLE)));
+    assert!(retry.is_retryable(&error(StatusCode::TOO_MANY_REQUESTS)));
+    assert!(!retry.is_retryable(&error(StatusCode::NOT_FOUND)));
+    assert!(!retry.is_retryable(&error(StatusCode::IM_A_TEAPOT)));
+
+    let retry = RetryPolicy {
+        retryable_errors: vec![NodeClientError::NotFound],
+        ..RetryPolicy::default()
+    };
+    assert!(retry.is_retryable(&error(StatusCode::NOT_FOUND)));
+    assert!(!retry.is_retryable(&error(StatusCode::SERVICE_UNAVAILABLE)));
+
+    let retry = RetryPolicy {
+        retryable_errors: vec![unknown(StatusCode::IM_A_TEAPOT)],
+        ..RetryPolicy::default()
+    };
+    assert!(retry.is_retryable(&error(StatusCode::IM_A_TEAPOT)));
+    assert!(!retry.is_retryable(&error(StatusCode::CONFLICT)));
+    assert!(!retry.is_retryable(&error(StatusCode::SERVICE_UNAVAILABLE)));
+}
+
+#[test]
+fn default_policy_agrees_with_is_retryable() {
+    let retry = RetryPolicy::default();
+    let statuses = (400..600).filter_map(|status| StatusCode::from_u16(status).ok());
+    for status in statuses {
+        for error in [unknown(status), NodeClientError::decode(status, "")] {
+            assert_eq!(
+                retry.is_retryable(&ConnectorError::remote(error.clone())),
+                error.is_retryable(),
+                "{}",
+                error
+            );
+        }
+    }
+}
+
+#[test]
+fn display_leaves_the_cause_to_the_source() {
+    let error = ConnectorError::from(NodeClientError::NotEmpty);
+    assert_eq!(error.to_string(), "the request was rejected by the node");
+    let source = error.source().unwrap().downcast_ref::<NodeClientError>();
+    assert_eq!(source, Some(&NodeClientError::NotEmpty));
+
+    let error = ConnectorError::local(std::io::Error::other("disk on fire"));
+    assert_eq!(error.to_string(), "the request failed");
+    assert_eq!(error.source().unwrap().to_string(), "disk on fire");
+}
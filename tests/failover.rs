@@ -0,0 +1,47 @@
+use meowith_connector::connector::connector::MeowithConnector;
+use meowith_connector::connector::retry::RetryPolicy;
+use meowith_connector::testing::{MockBucket, MockNode};
+use std::net::TcpListener;
+
+/// An address nothing listens on, so connecting to it is refused.
+fn refused_addr() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+#[tokio::test]
+async fn fails_over_to_the_next_node() {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    node.insert_file(&bucket, "a.txt", "a").unwrap();
+
+    let dead = refused_addr();
+    let connector =
+        MeowithConnector::builder("token", bucket.bucket_id, bucket.app_id, dead.clone())
+            .node(node.addr().to_string())
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+    // Both an idempotent and a non idempotent request fail over on refused connections.
+    assert_eq!(connector.stat_resource("a.txt").await.unwrap().size, 1);
+    connector.create_directory("docs").await.unwrap();
+    assert_eq!(connector.healthy_nodes(), [node.addr().to_string()]);
+}
+
+#[tokio::test]
+async fn fails_when_every_node_is_down() {
+    let bucket = MockBucket::new("files");
+    let connector =
+        MeowithConnector::builder("token", bucket.bucket_id, bucket.app_id, refused_addr())
+            .node(refused_addr())
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap();
+
+    let err = connector.stat_resource("a.txt").await.unwrap_err();
+    assert!(err.remote_error().is_none());
+    assert_eq!(err.context().unwrap().operation, "stat_resource");
+    assert!(connector.healthy_nodes().is_empty());
+}
@@ -0,0 +1,98 @@
+mod common;
+
+use bytes::Bytes;
+use chrono::Utc;
+use common::RecordingNode;
+use meowith_connector::connector::connector::MeowithConnector;
+use meowith_connector::connector::journal::{JsonFileSessionStore, PendingUpload, SessionStore};
+use meowith_connector::testing::{MockBucket, MockNode};
+use reqwest::StatusCode;
+use uuid::Uuid;
+
+fn pending(code: &str) -> PendingUpload {
+    PendingUpload {
+        path: "a.txt".to_string(),
+        size: 10,
+        code: code.to_string(),
+        source_id: "/tmp/a.txt".to_string(),
+        validity: 60,
+        started: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn save_load_and_remove() {
+    let dir = tempfile::tempdir().unwrap();
+    let store = JsonFileSessionStore::new(dir.path().join("journal.json"));
+    assert!(store.pending().await.unwrap().is_empty());
+
+    store.save(&pending("a")).await.unwrap();
+    store.save(&pending("b")).await.unwrap();
+    let mut replaced = pending("a");
+    replaced.size = 20;
+    store.save(&replaced).await.unwrap();
+
+    let uploads = store.pending().await.unwrap();
+    let codes: Vec<_> = uploads.iter().map(|it| it.code.as_str()).collect();
+    assert_eq!(codes, ["b", "a"]);
+    assert_eq!(uploads[1].size, 20);
+
+    store.remove("b").await.unwrap();
+    let uploads = store.pending().await.unwrap();
+    assert_eq!(uploads.len(), 1);
+    assert_eq!(uploads[0].code, "a");
+    assert!(!dir.path().join("journal.json.tmp").exists());
+}
+
+#[tokio::test]
+async fn resumes_after_a_restart() {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    let connector = node.connector(&bucket);
+    let dir = tempfile::tempdir().unwrap();
+    let journal = dir.path().join("journal.json");
+    let data = Bytes::from("0123456789");
+
+    // The first process starts the upload and dies after sending part of it.
+    {
+        let store = JsonFileSessionStore::new(&journal);
+        let session = connector.start_upload_session("a.txt", 10).await.unwrap();
+        store
+            .save(&PendingUpload {
+                code: session.code.clone(),
+                ..pending("")
+            })
+            .await
+            .unwrap();
+        let _ = connector.put_file(session, data.slice(..5).into()).await;
+    }
+
+    let store = JsonFileSessionStore::new(&journal);
+    let uploads = store.pending().await.unwrap();
+    assert_eq!(uploads.len(), 1);
+    connector
+        .resume_pending_upload(&uploads[0], &data, &store)
+        .await
+        .unwrap();
+    assert_eq!(node.file(&bucket, "a.txt").unwrap(), data);
+    assert!(store.pending().await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn forgets_uploads_the_node_no_longer_knows() {
+    // A node answering with a bare 404 rather than a `NoSuchSession` code.
+    let node = RecordingNode::start(StatusCode::NOT_FOUND, "").await;
+    let connector = MeowithConnector::new("token", Uuid::new_v4(), Uuid::new_v4(), node.addr);
+    let dir = tempfile::tempdir().unwrap();
+    let store = JsonFileSessionStore::new(dir.path().join("journal.json"));
+    let upload = pending(&Uuid::new_v4().to_string());
+    store.save(&upload).await.unwrap();
+
+    let err = connector
+        .resume_pending_upload(&upload, &Bytes::from("0123456789"), &store)
+        .await
+        .unwrap_err();
+    assert!(err.remote_error().unwrap().is_not_found());
+    assert!(store.pending().await.unwrap().is_empty());
+}
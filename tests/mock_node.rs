@@ -0,0 +1,398 @@
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use meowith_connector::connector::connector::MeowithConnector;
+use meowith_connector::connector::walk::{WalkOptions, WalkOrder};
+use meowith_connector::dto::range::{DownloadRange, Range};
+use meowith_connector::error::{ConnectorError, NodeClientError};
+use meowith_connector::testing::{MockBucket, MockNode};
+use reqwest::StatusCode;
+use std::time::Duration;
+
+async fn setup(bucket: MockBucket) -> (MockNode, MockBucket, MeowithConnector) {
+    let node = MockNode::start().await.unwrap();
+    node.add_bucket(&bucket);
+    let connector = node.connector(&bucket);
+    (node, bucket, connector)
+}
+
+fn remote_error<T>(result: Result<T, ConnectorError>) -> NodeClientError {
+    match result {
+        Ok(_) => panic!("expected an error"),
+        Err(err) => err
+            .remote_error()
+            .cloned()
+            .expect("expected a remote error"),
+    }
+}
+
+#[tokio::test]
+async fn upload_and_download() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    connector.create_directory("docs").await.unwrap();
+    connector
+        .upload_oneshot("hello world".into(), "docs/a.txt", 11)
+        .await
+        .unwrap();
+    assert_eq!(node.file(&bucket, "docs/a.txt").unwrap(), "hello world");
+
+    let response = connector.download_file("docs/a.txt").await.unwrap();
+    assert_eq!(response.length, 11);
+    assert_eq!(response.name, "a.txt");
+    assert_eq!(response.bytes().await.unwrap(), "hello world");
+
+    let entity = connector.stat_resource("docs/a.txt").await.unwrap();
+    assert_eq!(entity.size, 11);
+    assert!(!entity.is_dir);
+}
+
+#[tokio::test]
+async fn ranged_downloads() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    node.insert_file(&bucket, "a.bin", "0123456789").unwrap();
+
+    let response = connector
+        .download_file_range("a.bin", DownloadRange::new(Some(2), Some(5)))
+        .await
+        .unwrap();
+    let range = response.content_range.unwrap();
+    assert_eq!((range.start, range.end, range.total), (2, 5, Some(10)));
+    assert_eq!(response.bytes().await.unwrap(), "2345");
+
+    let response = connector
+        .download_file_range("a.bin", DownloadRange::new(None, Some(3)))
+        .await
+        .unwrap();
+    assert_eq!(response.bytes().await.unwrap(), "789");
+
+    let result = connector
+        .download_file_range("a.bin", DownloadRange::new(Some(10), None))
+        .await;
+    assert_eq!(remote_error(result), NodeClientError::RangeUnsatisfiable);
+}
+
+#[tokio::test]
+async fn download_to_path() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    node.insert_file(&bucket, "a.bin", vec![7u8; 4096]).unwrap();
+
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("a.bin");
+    assert_eq!(
+        connector.download_to_path("a.bin", &local).await.unwrap(),
+        4096
+    );
+    assert_eq!(std::fs::read(&local).unwrap(), vec![7u8; 4096]);
+}
+
+#[tokio::test]
+async fn entity_errors() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    node.insert_directory(&bucket, "docs").unwrap();
+    node.insert_file(&bucket, "docs/a.txt", "a").unwrap();
+
+    let err = connector.create_directory("docs").await.unwrap_err();
+    assert_eq!(err.remote_error(), Some(&NodeClientError::EntityExists));
+    assert_eq!(err.status(), Some(StatusCode::CONFLICT));
+
+    let result = connector.rename_file("docs/a.txt", "docs").await;
+    assert_eq!(remote_error(result), NodeClientError::EntityExists);
+
+    let result = connector.delete_directory("docs", false).await;
+    assert_eq!(remote_error(result), NodeClientError::NotEmpty);
+
+    let result = connector.stat_resource("missing").await;
+    assert!(remote_error(result).is_not_found());
+
+    let result = connector
+        .upload_oneshot("a".into(), "missing/a.txt", 1)
+        .await;
+    assert_eq!(remote_error(result), NodeClientError::NotFound);
+
+    connector.delete_directory("docs", true).await.unwrap();
+    assert!(node.file(&bucket, "docs/a.txt").is_none());
+}
+
+#[tokio::test]
+async fn rename_directory_moves_its_content() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    node.insert_directory(&bucket, "a").unwrap();
+    node.insert_directory(&bucket, "a/b").unwrap();
+    node.insert_file(&bucket, "a/b/c.txt", "c").unwrap();
+
+    connector.rename_directory("a", "z").await.unwrap();
+    assert_eq!(node.file(&bucket, "z/b/c.txt").unwrap(), "c");
+    assert!(node.file(&bucket, "a/b/c.txt").is_none());
+    connector.rename_file("z/b/c.txt", "d.txt").await.unwrap();
+    assert_eq!(node.file(&bucket, "d.txt").unwrap(), "c");
+}
+
+#[tokio::test]
+async fn quota() {
+    let (_node, _bucket, connector) = setup(MockBucket::new("files").quota(10)).await;
+    connector
+        .upload_oneshot(vec![0u8; 8].into(), "a.bin", 8)
+        .await
+        .unwrap();
+
+    let result = connector
+        .upload_oneshot(vec![0u8; 4].into(), "b.bin", 4)
+        .await;
+    assert_eq!(remote_error(result), NodeClientError::InsufficientStorage);
+
+    let result = connector.start_upload_session("c.bin", 4).await;
+    assert_eq!(remote_error(result), NodeClientError::InsufficientStorage);
+
+    // Replacing a file only counts the difference.
+    connector
+        .upload_oneshot(vec![0u8; 10].into(), "a.bin", 10)
+        .await
+        .unwrap();
+    let info = connector.fetch_bucket_info().await.unwrap();
+    assert_eq!((info.quota, info.space_taken, info.file_count), (10, 10, 1));
+}
+
+#[tokio::test]
+async fn listings() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    node.insert_directory(&bucket, "docs").unwrap();
+    for i in 0..5 {
+        node.insert_file(&bucket, &format!("docs/{i}.txt"), "x")
+            .unwrap();
+    }
+    node.insert_file(&bucket, "root.txt", "x").unwrap();
+
+    let page = connector
+        .list_directory(
+            "docs",
+            Some(Range {
+                start: Some(1),
+                end: Some(3),
+            }),
+        )
+        .await
+        .unwrap();
+    let names: Vec<_> = page.entities.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["1.txt", "2.txt"]);
+
+    let files: Vec<_> = connector
+        .list_bucket_files_stream(2)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(files.len(), 6);
+    let directories = connector.list_bucket_directories(None).await.unwrap();
+    assert_eq!(directories.entities.len(), 1);
+
+    let root = connector.list_directory("", None).await.unwrap();
+    let names: Vec<_> = root.entities.iter().map(|e| e.name.as_str()).collect();
+    assert_eq!(names, ["docs", "root.txt"]);
+
+    let mut walked: Vec<_> = connector
+        .walk("", WalkOptions::default())
+        .map_ok(|(path, _)| path)
+        .try_collect()
+        .await
+        .unwrap();
+    walked.sort();
+    assert_eq!(walked.len(), 7);
+    assert_eq!(walked[0], "docs");
+}
+
+#[tokio::test]
+async fn durable_upload() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    let data = Bytes::from(vec![1u8; 100_000]);
+    connector.upload_durable("big.bin", &data).await.unwrap();
+    assert_eq!(node.file(&bucket, "big.bin").unwrap(), data);
+    assert_eq!(node.pending_sessions(), 0);
+}
+
+#[tokio::test]
+async fn upload_session_resumes_from_the_uploaded_offset() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    let session = connector.start_upload_session("a.txt", 10).await.unwrap();
+
+    // A short body leaves the session open with what was received.
+    let result = connector.put_file(session.clone(), "01234".into()).await;
+    assert_eq!(remote_error(result), NodeClientError::BadRequest);
+    let resumed = connector
+        .resume_upload_session(session.clone())
+        .await
+        .unwrap();
+    assert_eq!(resumed.uploaded_size, 5);
+
+    connector
+        .put_file(session.clone(), "56789".into())
+        .await
+        .unwrap();
+    assert_eq!(node.file(&bucket, "a.txt").unwrap(), "0123456789");
+    let result = connector.resume_upload_session(session).await;
+    assert_eq!(remote_error(result), NodeClientError::NoSuchSession);
+}
+
+#[tokio::test]
+async fn upload_sessions_expire() {
+    let (node, _bucket, connector) = setup(MockBucket::new("files")).await;
+    node.set_session_validity(Duration::from_millis(50));
+    let session = connector.start_upload_session("a.txt", 10).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let result = connector.resume_upload_session(session).await;
+    assert_eq!(remote_error(result), NodeClientError::NoSuchSession);
+    assert_eq!(node.pending_sessions(), 0);
+}
+
+#[tokio::test]
+async fn unknown_bucket() {
+    let node = MockNode::start().await.unwrap();
+    let connector = node.connector(&MockBucket::new("missing"));
+    let result = connector.fetch_bucket_info().await;
+    assert_eq!(remote_error(result), NodeClientError::NotFound);
+}
+
+#[tokio::test]
+async fn listing_streams_fetch_every_page() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    for i in 0..5 {
+        node.insert_file(&bucket, &format!("{i}.txt"), "data")
+            .unwrap();
+    }
+
+    let names: Vec<_> = connector
+        .list_directory_stream("", 2)
+        .map_ok(|entity| entity.name)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(names, ["0.txt", "1.txt", "2.txt", "3.txt", "4.txt"]);
+}
+
+async fn walked_paths(connector: &MeowithConnector, options: WalkOptions) -> Vec<String> {
+    let mut paths: Vec<_> = connector
+        .walk("", options)
+        .map_ok(|(path, _)| path)
+        .try_collect()
+        .await
+        .unwrap();
+    paths.sort();
+    paths
+}
+
+#[tokio::test]
+async fn walk_max_depth() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    for dir in ["a", "a/b", "a/b/c"] {
+        node.insert_directory(&bucket, dir).unwrap();
+    }
+    node.insert_file(&bucket, "a/b/c/1.txt", "1").unwrap();
+
+    let depth = |max_depth| WalkOptions {
+        max_depth,
+        ..WalkOptions::default()
+    };
+    assert!(walked_paths(&connector, depth(Some(0))).await.is_empty());
+    assert_eq!(walked_paths(&connector, depth(Some(1))).await, ["a"]);
+    assert_eq!(walked_paths(&connector, depth(Some(2))).await, ["a", "a/b"]);
+    assert_eq!(
+        walked_paths(&connector, depth(None)).await,
+        ["a", "a/b", "a/b/c", "a/b/c/1.txt"]
+    );
+}
+
+#[tokio::test]
+async fn breadth_first_walk_goes_level_by_level() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    for dir in ["a", "a/x", "a/x/y", "b", "b/z"] {
+        node.insert_directory(&bucket, dir).unwrap();
+    }
+    node.insert_file(&bucket, "a/x/y/1.txt", "1").unwrap();
+    node.insert_file(&bucket, "c.txt", "c").unwrap();
+
+    let options = WalkOptions {
+        order: WalkOrder::BreadthFirst,
+        ..WalkOptions::default()
+    };
+    let walked: Vec<_> = connector
+        .walk("", options)
+        .map_ok(|(path, _)| path)
+        .try_collect()
+        .await
+        .unwrap();
+    let depths: Vec<_> = walked
+        .iter()
+        .map(|path| path.matches('/').count())
+        .collect();
+    assert!(depths.is_sorted(), "{walked:?}");
+    assert_eq!(walked.len(), 7);
+}
+
+#[tokio::test]
+async fn walk_lists_at_most_concurrency_directories_at_once() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    for i in 0..6 {
+        node.insert_directory(&bucket, &format!("dir{i}")).unwrap();
+    }
+    node.set_latency(Duration::from_millis(50));
+
+    let options = WalkOptions {
+        concurrency: 2,
+        ..WalkOptions::default()
+    };
+    assert_eq!(walked_paths(&connector, options).await.len(), 6);
+    assert_eq!(node.peak_concurrent_requests(), 2);
+}
+
+#[tokio::test]
+async fn sorted_walk_is_depth_first_in_name_order() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    for dir in ["a", "a/x", "a-b"] {
+        node.insert_directory(&bucket, dir).unwrap();
+    }
+    node.insert_file(&bucket, "a/x/1.csv", "1").unwrap();
+    node.insert_file(&bucket, "a-b/2.csv", "2").unwrap();
+
+    let options = WalkOptions {
+        order: WalkOrder::Sorted,
+        ..WalkOptions::default()
+    };
+    let walked: Vec<_> = connector
+        .walk("", options)
+        .map_ok(|(path, _)| path)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(walked, ["a", "a/x", "a/x/1.csv", "a-b", "a-b/2.csv"]);
+}
+
+#[tokio::test]
+async fn malformed_pagination_is_rejected() {
+    let (node, bucket, connector) = setup(MockBucket::new("files")).await;
+    for i in 0..3 {
+        node.insert_file(&bucket, &format!("{i}.txt"), "x").unwrap();
+    }
+    let page = connector
+        .list_bucket_files(Some(Range {
+            start: Some(1),
+            end: None,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(page.entities.len(), 2);
+
+    for query in ["start=1-2", "start=x", "start=-1", "start=", "end=2-"] {
+        let url = format!(
+            "{}/api/bucket/list/files/{}/{}?{}",
+            node.addr(),
+            bucket.app_id,
+            bucket.bucket_id,
+            query
+        );
+        let response = reqwest::Client::new()
+            .get(url)
+            .bearer_auth("token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", query);
+    }
+}
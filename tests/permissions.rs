@@ -0,0 +1,105 @@
+use meowith_connector::dto::permission::BucketPermissions;
+use meowith_connector::dto::request::ScopedPermission;
+use std::str::FromStr;
+use uuid::Uuid;
+
+#[test]
+fn every_flag_round_trips_through_allowance() {
+    for (name, flag) in BucketPermissions::all().iter_names() {
+        let allowance: u64 = flag.into();
+        assert_eq!(BucketPermissions::from(allowance), flag, "{}", name);
+    }
+}
+
+#[test]
+fn every_flag_round_trips_through_display() {
+    for (name, flag) in BucketPermissions::all().iter_names() {
+        let parsed = BucketPermissions::from_str(&flag.to_string()).unwrap();
+        assert_eq!(parsed, flag, "{}", name);
+        assert_eq!(
+            BucketPermissions::from_str(name).unwrap(),
+            flag,
+            "parsing {}",
+            name
+        );
+    }
+}
+
+#[test]
+fn combined_flags_display_and_parse() {
+    let perms = BucketPermissions::READ | BucketPermissions::RENAME;
+    assert_eq!(perms.to_string(), "READ | RENAME");
+    assert_eq!(BucketPermissions::from_str("READ | RENAME").unwrap(), perms);
+    assert!(BucketPermissions::from_str("READ | FLY").is_err());
+}
+
+#[test]
+fn unknown_bits_are_retained() {
+    let allowance = BucketPermissions::READ.bits() | 1 << 40;
+    let perms = BucketPermissions::from_allowance(allowance);
+    assert_eq!(perms.allowance(), allowance);
+    assert_eq!(
+        BucketPermissions::from_str(&perms.to_string()).unwrap(),
+        perms
+    );
+}
+
+#[test]
+fn builder_sets_allowance() {
+    let bucket_id = Uuid::new_v4();
+    let scoped = ScopedPermission::builder(bucket_id)
+        .read()
+        .list()
+        .allow(BucketPermissions::WRITE)
+        .deny(BucketPermissions::LIST_BUCKET)
+        .build();
+
+    assert_eq!(scoped.bucket_id, bucket_id);
+    assert_eq!(
+        scoped.permissions(),
+        BucketPermissions::READ | BucketPermissions::WRITE | BucketPermissions::LIST_DIRECTORY
+    );
+    assert_eq!(
+        scoped.allowance,
+        (BucketPermissions::READ | BucketPermissions::WRITE | BucketPermissions::LIST_DIRECTORY)
+            .bits()
+    );
+}
+
+#[test]
+fn admin_contains_every_permission() {
+    assert_eq!(BucketPermissions::ADMIN, BucketPermissions::all());
+    let scoped = ScopedPermission::builder(Uuid::new_v4()).admin().build();
+    assert!(scoped.permissions().contains(BucketPermissions::DELETE));
+}
+
+#[test]
+fn composite_flags_contain_their_bits() {
+    assert_eq!(
+        BucketPermissions::LIST.bits(),
+        (1 << 5) | (1 << 6),
+        "LIST_DIRECTORY | LIST_BUCKET"
+    );
+    assert_eq!(
+        BucketPermissions::LIST,
+        BucketPermissions::LIST_DIRECTORY | BucketPermissions::LIST_BUCKET
+    );
+    assert!(!BucketPermissions::LIST.intersects(
+        BucketPermissions::READ | BucketPermissions::WRITE | BucketPermissions::FETCH_BUCKET_INFO
+    ));
+
+    assert_eq!(BucketPermissions::ADMIN.bits(), 0xff);
+    for flag in [
+        BucketPermissions::READ,
+        BucketPermissions::WRITE,
+        BucketPermissions::OVERWRITE,
+        BucketPermissions::DELETE,
+        BucketPermissions::RENAME,
+        BucketPermissions::LIST_DIRECTORY,
+        BucketPermissions::LIST_BUCKET,
+        BucketPermissions::FETCH_BUCKET_INFO,
+    ] {
+        assert!(BucketPermissions::ADMIN.contains(flag), "{}", flag);
+    }
+    assert!(BucketPermissions::ADMIN.contains(BucketPermissions::LIST));
+}
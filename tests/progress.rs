@@ -0,0 +1,81 @@
+use bytes::Bytes;
+use meowith_connector::connector::progress::{Progress, ProgressReporter};
+use meowith_connector::testing::{MockBucket, MockNode};
+use std::sync::{Arc, Mutex};
+
+const SIZE: u64 = 100_000;
+
+fn recorder() -> (ProgressReporter, Arc<Mutex<Vec<Progress>>>) {
+    let reports = Arc::new(Mutex::new(Vec::new()));
+    let sink = reports.clone();
+    let reporter = ProgressReporter::new(move |progress| sink.lock().unwrap().push(progress));
+    (reporter, reports)
+}
+
+/// Counts never go backwards and end at the total.
+fn assert_complete(reports: &[Progress]) {
+    assert!(reports.len() > 1, "expected several reports");
+    for pair in reports.windows(2) {
+        assert!(pair[0].transferred <= pair[1].transferred, "{:?}", pair);
+    }
+    let last = reports.last().unwrap();
+    assert_eq!(last.total, Some(SIZE));
+    assert_eq!(last.transferred, SIZE);
+    assert_eq!(last.eta, Some(std::time::Duration::ZERO));
+}
+
+#[tokio::test]
+async fn upload_progress() {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("a.bin");
+    std::fs::write(&local, vec![1u8; SIZE as usize]).unwrap();
+
+    let (reporter, reports) = recorder();
+    node.connector(&bucket)
+        .upload_file_with_progress(&local, "a.bin", &reporter)
+        .await
+        .unwrap();
+    assert_complete(&reports.lock().unwrap());
+}
+
+#[tokio::test]
+async fn download_progress() {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    node.insert_file(&bucket, "a.bin", vec![1u8; SIZE as usize])
+        .unwrap();
+    let dir = tempfile::tempdir().unwrap();
+
+    let (reporter, reports) = recorder();
+    let length = node
+        .connector(&bucket)
+        .download_to_path_with_progress("a.bin", dir.path().join("a.bin"), &reporter)
+        .await
+        .unwrap();
+    assert_eq!(length, SIZE);
+    assert_complete(&reports.lock().unwrap());
+}
+
+#[tokio::test]
+async fn watch_channel_closes_with_the_reporter() {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+
+    let (reporter, mut progress) = ProgressReporter::watch();
+    let data = Bytes::from(vec![1u8; SIZE as usize]);
+    node.connector(&bucket)
+        .upload_durable_with_progress("a.bin", &data, &reporter)
+        .await
+        .unwrap();
+
+    assert!(progress.has_changed().unwrap());
+    let latest = *progress.borrow_and_update();
+    assert_eq!((latest.transferred, latest.total), (SIZE, Some(SIZE)));
+    drop(reporter);
+    assert!(progress.changed().await.is_err());
+}
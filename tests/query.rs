@@ -0,0 +1,129 @@
+use chrono::{TimeZone, Utc};
+use futures_util::TryStreamExt;
+use meowith_connector::connector::connector::MeowithConnector;
+use meowith_connector::connector::query::{EntityKind, EntityQuery};
+use meowith_connector::dto::response::Entity;
+use meowith_connector::testing::{MockBucket, MockNode};
+
+fn entity(size: u64, is_dir: bool, day: u32) -> Entity {
+    let modified = Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap();
+    Entity {
+        name: "entity".to_string(),
+        dir: None,
+        dir_id: None,
+        size,
+        is_dir,
+        created: modified,
+        last_modified: modified,
+    }
+}
+
+/// A bucket with files at several depths.
+async fn setup() -> (MockNode, MeowithConnector) {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    for dir in ["dir", "dir/sub", "other", "reports", "reports/2024", "logs"] {
+        node.insert_directory(&bucket, dir).unwrap();
+    }
+    for file in [
+        "a.csv",
+        "a.txt",
+        "dir/a.txt",
+        "dir/sub/a.txt",
+        "other/a.txt",
+        "reports/summary.txt",
+        "reports/2024/q1.csv",
+        "reports/2024/summary.txt",
+        "logs/app.log",
+    ] {
+        node.insert_file(&bucket, file, "data").unwrap();
+    }
+    let connector = node.connector(&bucket);
+    (node, connector)
+}
+
+async fn query(connector: &MeowithConnector, patterns: &[&str]) -> Vec<String> {
+    let query = patterns
+        .iter()
+        .fold(EntityQuery::new(), |query, pattern| query.glob(pattern))
+        .kind(EntityKind::Files);
+    let mut paths: Vec<_> = connector
+        .query(&query)
+        .unwrap()
+        .map_ok(|(path, _)| path)
+        .try_collect()
+        .await
+        .unwrap();
+    paths.sort();
+    paths
+}
+
+#[tokio::test]
+async fn globs() {
+    let (_node, connector) = setup().await;
+    assert_eq!(
+        query(&connector, &["**/*.csv"]).await,
+        ["a.csv", "reports/2024/q1.csv"]
+    );
+    assert_eq!(query(&connector, &["dir/*.txt"]).await, ["dir/a.txt"]);
+    assert_eq!(query(&connector, &["/dir/a.txt"]).await, ["dir/a.txt"]);
+    assert_eq!(query(&connector, &["*.txt"]).await, ["a.txt"]);
+    assert_eq!(
+        query(&connector, &["dir/**"]).await,
+        ["dir/a.txt", "dir/sub/a.txt"]
+    );
+    assert_eq!(query(&connector, &[]).await.len(), 9);
+
+    assert!(connector.query(&EntityQuery::new().glob("a/[")).is_err());
+}
+
+#[tokio::test]
+async fn globs_with_different_literal_prefixes() {
+    let (_node, connector) = setup().await;
+    assert_eq!(
+        query(&connector, &["reports/2024/*.csv", "reports/*/summary.txt"]).await,
+        ["reports/2024/q1.csv", "reports/2024/summary.txt"]
+    );
+    assert_eq!(
+        query(&connector, &["reports/2024/*.csv", "logs/*.log"]).await,
+        ["logs/app.log", "reports/2024/q1.csv"]
+    );
+}
+
+#[test]
+fn predicates() {
+    let query = EntityQuery::new().size(10..=20);
+    assert!(!query.matches_entity(&entity(9, false, 1)));
+    assert!(query.matches_entity(&entity(10, false, 1)));
+    assert!(query.matches_entity(&entity(20, false, 1)));
+    assert!(!query.matches_entity(&entity(21, false, 1)));
+
+    let query = EntityQuery::new().size(..10);
+    assert!(query.matches_entity(&entity(9, false, 1)));
+    assert!(!query.matches_entity(&entity(10, false, 1)));
+
+    // Empty ranges match nothing, not even empty entities.
+    assert!(!EntityQuery::new()
+        .size(..0)
+        .matches_entity(&entity(0, false, 1)));
+    assert!(!EntityQuery::new()
+        .size(0..0)
+        .matches_entity(&entity(0, false, 1)));
+
+    let query = EntityQuery::new()
+        .modified_since(Utc.with_ymd_and_hms(2024, 1, 2, 0, 0, 0).unwrap())
+        .modified_before(Utc.with_ymd_and_hms(2024, 1, 4, 0, 0, 0).unwrap());
+    assert!(!query.matches_entity(&entity(0, false, 1)));
+    assert!(query.matches_entity(&entity(0, false, 2)));
+    assert!(query.matches_entity(&entity(0, false, 3)));
+    assert!(!query.matches_entity(&entity(0, false, 4)));
+
+    let files = EntityQuery::new().kind(EntityKind::Files);
+    let directories = EntityQuery::new().kind(EntityKind::Directories);
+    assert!(files.matches_entity(&entity(0, false, 1)));
+    assert!(!files.matches_entity(&entity(0, true, 1)));
+    assert!(directories.matches_entity(&entity(0, true, 1)));
+    assert!(!directories.matches_entity(&entity(0, false, 1)));
+    assert!(EntityQuery::new().matches_entity(&entity(0, true, 1)));
+}
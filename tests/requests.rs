@@ -0,0 +1,510 @@
+mod common;
+
+use axum::http::{Method, StatusCode};
+use common::RecordingNode;
+use futures_util::TryStreamExt;
+use meowith_connector::connector::connector::MeowithConnector;
+use meowith_connector::connector::retry::RetryPolicy;
+use meowith_connector::dto::range::{DownloadRange, Range};
+use meowith_connector::dto::response::UploadSessionStartResponse;
+use meowith_connector::error::NodeClientError;
+use reqwest::Body;
+use serde_json::json;
+use uuid::Uuid;
+
+const APP: Uuid = Uuid::from_u128(0xa);
+const BUCKET: Uuid = Uuid::from_u128(0xb);
+const SESSION: Uuid = Uuid::from_u128(0xc);
+
+fn connector(node: &RecordingNode) -> MeowithConnector {
+    MeowithConnector::builder("token", BUCKET, APP, node.addr.clone())
+        .retry_policy(RetryPolicy::none())
+        .build()
+        .unwrap()
+}
+
+fn entity(name: &str) -> serde_json::Value {
+    json!({
+        "name": name,
+        "size": 12,
+        "is_dir": false,
+        "created": "2024-01-01T00:00:00Z",
+        "last_modified": "2024-01-02T00:00:00Z",
+    })
+}
+
+fn session() -> UploadSessionStartResponse {
+    UploadSessionStartResponse {
+        code: SESSION.to_string(),
+        validity: 30,
+        uploaded: 0,
+    }
+}
+
+#[tokio::test]
+async fn upload_oneshot() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node)
+        .upload_oneshot(Body::from("hello"), "dir/a b.txt", 5)
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(
+        request.path,
+        format!("/api/file/upload/oneshot/{APP}/{BUCKET}/dir%2Fa%20b.txt")
+    );
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+    assert_eq!(request.header("content-length"), Some("5"));
+    assert_eq!(request.body, "hello");
+}
+
+#[tokio::test]
+async fn delete_file() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).delete_file("dir/a.txt").await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(
+        request.path,
+        format!("/api/file/delete/{APP}/{BUCKET}/dir%2Fa.txt")
+    );
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+    assert!(request.body.is_empty());
+}
+
+#[tokio::test]
+async fn rename_file() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node)
+        .rename_file("a.txt", "dir/b.txt")
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(
+        request.path,
+        format!("/api/file/rename/{APP}/{BUCKET}/a.txt")
+    );
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    assert_eq!(request.json(), json!({ "to": "dir/b.txt" }));
+}
+
+#[tokio::test]
+async fn download_file() {
+    let node = RecordingNode::start_with_headers(
+        StatusCode::OK,
+        vec![
+            ("x-file-content-length", "5".to_string()),
+            ("content-type", "text/plain".to_string()),
+            (
+                "content-disposition",
+                "attachment; filename=\"b.txt\"".to_string(),
+            ),
+        ],
+        "hello",
+    )
+    .await;
+    let response = connector(&node).download_file("dir/a.txt").await.unwrap();
+    assert_eq!(response.length, 5);
+    assert_eq!(response.name, "b.txt");
+    assert_eq!(response.mime, mime::TEXT_PLAIN);
+    assert_eq!(response.content_range, None);
+    assert_eq!(response.bytes().await.unwrap(), "hello");
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(
+        request.path,
+        format!("/api/file/download/{APP}/{BUCKET}/dir%2Fa.txt")
+    );
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+    assert_eq!(request.header("range"), None);
+}
+
+#[tokio::test]
+async fn download_file_range() {
+    let node = RecordingNode::start_with_headers(
+        StatusCode::PARTIAL_CONTENT,
+        vec![
+            ("x-file-content-length", "5".to_string()),
+            ("content-type", "text/plain".to_string()),
+            ("content-range", "bytes 1-3/5".to_string()),
+        ],
+        "ell",
+    )
+    .await;
+    let response = connector(&node)
+        .download_file_range("dir/a.txt", DownloadRange::new(Some(1), Some(3)))
+        .await
+        .unwrap();
+    assert_eq!(response.name, "a.txt");
+    let range = response.content_range.unwrap();
+    assert_eq!((range.start, range.end, range.total), (1, 3, Some(5)));
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.header("range"), Some("bytes=1-3"));
+}
+
+#[tokio::test]
+async fn download_file_without_content_type() {
+    let node = RecordingNode::start_with_headers(
+        StatusCode::OK,
+        vec![("x-file-content-length", "5".to_string())],
+        "hello",
+    )
+    .await;
+    let response = connector(&node).download_file("a.txt").await.unwrap();
+    assert_eq!(response.mime, mime::APPLICATION_OCTET_STREAM);
+    assert_eq!(response.bytes().await.unwrap(), "hello");
+}
+
+#[tokio::test]
+async fn create_directory() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node).create_directory("dir/sub").await.unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(
+        request.path,
+        format!("/api/directory/create/{APP}/{BUCKET}/dir%2Fsub")
+    );
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+    assert!(request.body.is_empty());
+}
+
+#[tokio::test]
+async fn rename_directory() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node)
+        .rename_directory("dir", "other")
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(
+        request.path,
+        format!("/api/directory/rename/{APP}/{BUCKET}/dir")
+    );
+    assert_eq!(request.json(), json!({ "to": "other" }));
+}
+
+#[tokio::test]
+async fn delete_directory() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node)
+        .delete_directory("dir", true)
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::DELETE);
+    assert_eq!(
+        request.path,
+        format!("/api/directory/delete/{APP}/{BUCKET}/dir")
+    );
+    assert_eq!(request.json(), json!({ "recursive": true }));
+}
+
+#[tokio::test]
+async fn list_bucket_files() {
+    let node = RecordingNode::json(json!({ "entities": [entity("a.txt")] })).await;
+    let list = connector(&node)
+        .list_bucket_files(Some(Range {
+            start: Some(0),
+            end: Some(10),
+        }))
+        .await
+        .unwrap();
+    assert_eq!(list.entities.len(), 1);
+    assert_eq!(list.entities[0].name, "a.txt");
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(
+        request.path,
+        format!("/api/bucket/list/files/{APP}/{BUCKET}")
+    );
+    assert_eq!(request.query.as_deref(), Some("start=0&end=10"));
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+}
+
+#[tokio::test]
+async fn listing_stream_stops_at_a_short_page() {
+    let node = RecordingNode::json(json!({ "entities": [entity("a.txt")] })).await;
+    let files: Vec<_> = connector(&node)
+        .list_bucket_files_stream(2)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(node.request().query.as_deref(), Some("start=0&end=2"));
+}
+
+#[tokio::test]
+async fn list_bucket_directories() {
+    let node = RecordingNode::json(json!({ "entities": [] })).await;
+    connector(&node)
+        .list_bucket_directories(None)
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(
+        request.path,
+        format!("/api/bucket/list/directories/{APP}/{BUCKET}")
+    );
+    assert_eq!(request.query, None);
+}
+
+#[tokio::test]
+async fn list_directory() {
+    let node = RecordingNode::json(json!({ "entities": [] })).await;
+    connector(&node)
+        .list_directory(
+            "dir/sub",
+            Some(Range {
+                start: Some(5),
+                end: Some(7),
+            }),
+        )
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(
+        request.path,
+        format!("/api/directory/list/{APP}/{BUCKET}/dir%2Fsub")
+    );
+    assert_eq!(request.query.as_deref(), Some("start=5&end=7"));
+}
+
+#[tokio::test]
+async fn stat_resource() {
+    let node = RecordingNode::json(entity("a.txt")).await;
+    let entity = connector(&node).stat_resource("dir/a.txt").await.unwrap();
+    assert_eq!(entity.size, 12);
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(
+        request.path,
+        format!("/api/bucket/stat/{APP}/{BUCKET}/dir%2Fa.txt")
+    );
+}
+
+#[tokio::test]
+async fn fetch_bucket_info() {
+    let node = RecordingNode::json(json!({
+        "app_id": APP,
+        "id": BUCKET,
+        "name": "bucket",
+        "encrypted": false,
+        "atomic_upload": true,
+        "quota": 1024,
+        "file_count": 1,
+        "space_taken": 12,
+        "created": "2024-01-01T00:00:00Z",
+        "last_modified": "2024-01-02T00:00:00Z",
+    }))
+    .await;
+    let bucket = connector(&node).fetch_bucket_info().await.unwrap();
+    assert_eq!(bucket.id, BUCKET);
+
+    let request = node.request();
+    assert_eq!(request.method, Method::GET);
+    assert_eq!(request.path, format!("/api/bucket/info/{APP}/{BUCKET}"));
+}
+
+#[tokio::test]
+async fn start_upload_session() {
+    let node = RecordingNode::json(json!({
+        "code": SESSION,
+        "validity": 30,
+        "uploaded": 0,
+    }))
+    .await;
+    let session = connector(&node)
+        .start_upload_session("dir/big.bin", 1024)
+        .await
+        .unwrap();
+    assert_eq!(session.code, SESSION.to_string());
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(
+        request.path,
+        format!("/api/file/upload/durable/{APP}/{BUCKET}/dir%2Fbig.bin")
+    );
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    assert_eq!(request.json(), json!({ "size": 1024 }));
+}
+
+#[tokio::test]
+async fn resume_upload_session() {
+    let node = RecordingNode::json(json!({ "uploaded_size": 512 })).await;
+    let resumed = connector(&node)
+        .resume_upload_session(session())
+        .await
+        .unwrap();
+    assert_eq!(resumed.uploaded_size, 512);
+
+    let request = node.request();
+    assert_eq!(request.method, Method::POST);
+    assert_eq!(
+        request.path,
+        format!("/api/file/upload/resume/{APP}/{BUCKET}")
+    );
+    assert_eq!(request.header("content-type"), Some("application/json"));
+    assert_eq!(request.json(), json!({ "session_id": SESSION }));
+}
+
+#[tokio::test]
+async fn put_file() {
+    let node = RecordingNode::start(StatusCode::OK, "").await;
+    connector(&node)
+        .put_file(session(), Body::from("chunk"))
+        .await
+        .unwrap();
+
+    let request = node.request();
+    assert_eq!(request.method, Method::PUT);
+    assert_eq!(
+        request.path,
+        format!("/api/file/upload/put/{APP}/{BUCKET}/{SESSION}")
+    );
+    assert_eq!(request.header("authorization"), Some("Bearer token"));
+    assert_eq!(request.header("content-type"), None);
+    assert_eq!(request.body, "chunk");
+}
+
+#[tokio::test]
+async fn error_response() {
+    let node = RecordingNode::start(StatusCode::CONFLICT, r#"{"code":"EntityExists"}"#).await;
+    let err = connector(&node).create_directory("dir").await.unwrap_err();
+
+    assert_eq!(err.remote_error(), Some(&NodeClientError::EntityExists));
+    assert_eq!(err.status(), Some(StatusCode::CONFLICT));
+    let context = err.context().unwrap();
+    assert_eq!(context.operation, "create_directory");
+    assert_eq!(context.path.as_deref(), Some("dir"));
+    assert_eq!(context.method, Some(Method::POST));
+    assert_eq!(
+        context.url.as_ref().unwrap().path(),
+        format!("/api/directory/create/{APP}/{BUCKET}/dir")
+    );
+}
+
+#[tokio::test]
+async fn download_to_path_restarts_when_the_range_is_ignored() {
+    let node = RecordingNode::start_with_headers(
+        StatusCode::OK,
+        vec![
+            ("x-file-content-length", "10".to_string()),
+            ("content-type", "text/plain".to_string()),
+        ],
+        "0123456789",
+    )
+    .await;
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("a.txt");
+    std::fs::write(dir.path().join("a.txt.part"), "xyz").unwrap();
+
+    let length = connector(&node)
+        .download_to_path("a.txt", &local)
+        .await
+        .unwrap();
+    assert_eq!(length, 10);
+    assert_eq!(std::fs::read(&local).unwrap(), b"0123456789");
+    assert_eq!(node.request().header("range"), Some("bytes=3-"));
+}
+
+#[tokio::test]
+async fn download_to_path_never_appends_another_range() {
+    let node = RecordingNode::start_with_headers(
+        StatusCode::PARTIAL_CONTENT,
+        vec![
+            ("x-file-content-length", "10".to_string()),
+            ("content-type", "text/plain".to_string()),
+            ("content-range", "bytes 5-9/10".to_string()),
+        ],
+        "56789",
+    )
+    .await;
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("a.txt");
+    std::fs::write(dir.path().join("a.txt.part"), "abc").unwrap();
+
+    connector(&node)
+        .download_to_path("a.txt", &local)
+        .await
+        .unwrap_err();
+    assert!(!local.exists());
+    assert!(std::fs::read(dir.path().join("a.txt.part"))
+        .unwrap()
+        .is_empty());
+
+    // The whole file is requested again after the mismatch.
+    let ranges: Vec<_> = node
+        .requests()
+        .iter()
+        .map(|request| request.header("range").map(str::to_string))
+        .collect();
+    assert_eq!(ranges, [Some("bytes=3-".to_string()), None]);
+}
+
+#[tokio::test]
+async fn invalid_content_range() {
+    let node = RecordingNode::start_with_headers(
+        StatusCode::PARTIAL_CONTENT,
+        vec![
+            ("x-file-content-length", "10".to_string()),
+            ("content-type", "text/plain".to_string()),
+            ("content-range", "bytes 5-3/10".to_string()),
+        ],
+        "",
+    )
+    .await;
+    let result = connector(&node)
+        .download_file_range("a.txt", DownloadRange::new(Some(5), Some(3)))
+        .await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn oversized_content_length_is_not_preallocated() {
+    let node = RecordingNode::start_with_headers(
+        StatusCode::OK,
+        vec![
+            ("x-file-content-length", "99999999999999".to_string()),
+            ("content-type", "text/plain".to_string()),
+        ],
+        "hello",
+    )
+    .await;
+    let response = connector(&node).download_file("a.txt").await.unwrap();
+    assert_eq!(response.body_length(), 99999999999999);
+    assert_eq!(response.bytes().await.unwrap(), "hello");
+}
+
+#[tokio::test]
+async fn open_ended_pagination_query() {
+    let node = RecordingNode::json(json!({ "entities": [] })).await;
+    connector(&node)
+        .list_bucket_files(Some(Range {
+            start: Some(5),
+            end: None,
+        }))
+        .await
+        .unwrap();
+    assert_eq!(node.request().query.as_deref(), Some("start=5-"));
+}
@@ -0,0 +1,181 @@
+mod common;
+
+use axum::http::StatusCode;
+use common::RecordingNode;
+use meowith_connector::connector::connector::MeowithConnector;
+use meowith_connector::connector::retry::RetryPolicy;
+use meowith_connector::dto::response::UploadSessionStartResponse;
+use meowith_connector::error::{ConnectorError, NodeClientError};
+use std::time::Duration;
+use uuid::Uuid;
+
+fn policy(max_attempts: u32) -> RetryPolicy {
+    RetryPolicy {
+        max_attempts,
+        initial_backoff: Duration::from_millis(1),
+        max_backoff: Duration::from_millis(5),
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    }
+}
+
+fn connector(node: &RecordingNode, retry: RetryPolicy) -> MeowithConnector {
+    MeowithConnector::builder("token", Uuid::nil(), Uuid::nil(), node.addr.clone())
+        .retry_policy(retry)
+        .build()
+        .unwrap()
+}
+
+#[test]
+fn backoff_grows_up_to_the_maximum() {
+    let retry = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        max_backoff: Duration::from_millis(500),
+        multiplier: 2.0,
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    };
+    let delays: Vec<_> = (1..=5).map(|retry_no| retry.backoff(retry_no)).collect();
+    assert_eq!(delays, [100, 200, 400, 500, 500].map(Duration::from_millis));
+}
+
+#[test]
+fn jitter_only_shortens_the_delay() {
+    let retry = RetryPolicy {
+        initial_backoff: Duration::from_millis(100),
+        jitter: 0.5,
+        ..RetryPolicy::default()
+    };
+    for _ in 0..100 {
+        let delay = retry.backoff(1);
+        assert!(delay >= Duration::from_millis(50), "{:?}", delay);
+        assert!(delay <= Duration::from_millis(100), "{:?}", delay);
+    }
+}
+
+#[test]
+fn classifies_remote_errors() {
+    let retry = RetryPolicy::default();
+    for error in [
+        NodeClientError::InternalError,
+        NodeClientError::TooManyRequests,
+        NodeClientError::Unavailable,
+    ] {
+        assert!(retry.is_retryable(&ConnectorError::remote(error)));
+    }
+    for error in [
+        NodeClientError::NotFound,
+        NodeClientError::BadRequest,
+        NodeClientError::EntityExists,
+    ] {
+        assert!(!retry.is_retryable(&ConnectorError::remote(error)));
+    }
+
+    let retry = RetryPolicy {
+        retryable_errors: vec![NodeClientError::NotFound],
+        ..RetryPolicy::default()
+    };
+    assert!(retry.is_retryable(&ConnectorError::remote(NodeClientError::NotFound)));
+    assert!(!retry.is_retryable(&ConnectorError::remote(NodeClientError::InternalError)));
+}
+
+#[tokio::test]
+async fn idempotent_requests_are_retried_up_to_max_attempts() {
+    let node = RecordingNode::start(StatusCode::SERVICE_UNAVAILABLE, "").await;
+    let err = connector(&node, policy(3))
+        .stat_resource("a.txt")
+        .await
+        .unwrap_err();
+    assert_eq!(err.remote_error(), Some(&NodeClientError::Unavailable));
+    assert_eq!(node.requests().len(), 3);
+}
+
+#[tokio::test]
+async fn non_idempotent_requests_are_not_retried() {
+    let node = RecordingNode::start(StatusCode::SERVICE_UNAVAILABLE, "").await;
+    let err = connector(&node, policy(3))
+        .create_directory("docs")
+        .await
+        .unwrap_err();
+    assert_eq!(err.remote_error(), Some(&NodeClientError::Unavailable));
+    assert_eq!(node.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn errors_which_are_not_retryable_end_the_request() {
+    let node = RecordingNode::start(StatusCode::NOT_FOUND, "").await;
+    let err = connector(&node, policy(3))
+        .stat_resource("a.txt")
+        .await
+        .unwrap_err();
+    assert!(err.remote_error().unwrap().is_not_found());
+    assert_eq!(node.requests().len(), 1);
+}
+
+#[tokio::test]
+async fn resuming_an_upload_session_is_retried() {
+    let node = RecordingNode::start(StatusCode::SERVICE_UNAVAILABLE, "").await;
+    let session = UploadSessionStartResponse {
+        code: Uuid::new_v4().to_string(),
+        validity: 60,
+        uploaded: 0,
+    };
+    let err = connector(&node, policy(3))
+        .resume_upload_session(session)
+        .await
+        .unwrap_err();
+    assert_eq!(err.remote_error(), Some(&NodeClientError::Unavailable));
+    assert_eq!(node.requests().len(), 3);
+}
+
+#[test]
+fn backoff_never_overflows() {
+    let retry = RetryPolicy {
+        max_attempts: 25,
+        multiplier: 10.0,
+        ..RetryPolicy::default()
+    };
+    for retry_no in [21, 25, 1000, u32::MAX] {
+        assert!(retry.backoff(retry_no) <= retry.max_backoff);
+    }
+
+    let retry = RetryPolicy {
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(retry.backoff(100), retry.max_backoff);
+    assert_eq!(retry.backoff(u32::MAX), retry.max_backoff);
+
+    let retry = RetryPolicy {
+        max_backoff: Duration::MAX,
+        multiplier: f64::INFINITY,
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(retry.backoff(1), retry.initial_backoff);
+    assert!(retry.backoff(2) > Duration::from_secs(1 << 40));
+}
+
+#[test]
+fn backoff_tolerates_invalid_factors() {
+    let retry = RetryPolicy {
+        multiplier: -2.0,
+        jitter: f64::NAN,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(retry.backoff(3), retry.initial_backoff);
+
+    let retry = RetryPolicy {
+        multiplier: f64::NAN,
+        jitter: -1.0,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(retry.backoff(3), retry.initial_backoff);
+
+    let retry = RetryPolicy {
+        initial_backoff: Duration::ZERO,
+        multiplier: f64::INFINITY,
+        ..RetryPolicy::default()
+    };
+    assert_eq!(retry.backoff(3), Duration::ZERO);
+}
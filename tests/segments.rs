@@ -0,0 +1,71 @@
+use bytes::Bytes;
+use futures_util::TryStreamExt;
+use meowith_connector::connector::download::SegmentOptions;
+use meowith_connector::testing::{MockBucket, MockNode};
+
+fn options(segments: u64) -> SegmentOptions {
+    SegmentOptions {
+        segments,
+        concurrency: 3,
+    }
+}
+
+#[test]
+fn splits_evenly() {
+    assert_eq!(
+        options(4).ranges(100),
+        [(0, 24), (25, 49), (50, 74), (75, 99)]
+    );
+}
+
+#[test]
+fn last_segment_takes_the_remainder() {
+    assert_eq!(options(3).ranges(10), [(0, 3), (4, 7), (8, 9)]);
+    assert_eq!(options(4).ranges(9), [(0, 2), (3, 5), (6, 8)]);
+}
+
+#[test]
+fn small_and_empty_files() {
+    assert!(options(4).ranges(0).is_empty());
+    assert_eq!(options(8).ranges(3), [(0, 0), (1, 1), (2, 2)]);
+    assert_eq!(options(0).ranges(5), [(0, 4)]);
+}
+
+#[tokio::test]
+async fn segmented_downloads() {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    let data: Bytes = (0..1001u32).map(|i| i as u8).collect();
+    node.insert_file(&bucket, "a.bin", data.clone()).unwrap();
+    node.insert_file(&bucket, "empty.bin", "").unwrap();
+    let connector = node.connector(&bucket);
+
+    let segments: Vec<Bytes> = connector
+        .download_segmented("a.bin", options(7))
+        .await
+        .unwrap()
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(segments.len(), 7);
+    assert_eq!(segments.concat(), data);
+
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("a.bin");
+    let size = connector
+        .download_segmented_to_path("a.bin", &local, options(7))
+        .await
+        .unwrap();
+    assert_eq!(size, 1001);
+    assert_eq!(std::fs::read(&local).unwrap(), data);
+    assert!(!dir.path().join("a.bin.part").exists());
+
+    let local = dir.path().join("empty.bin");
+    let size = connector
+        .download_segmented_to_path("empty.bin", &local, options(7))
+        .await
+        .unwrap();
+    assert_eq!(size, 0);
+    assert!(std::fs::read(&local).unwrap().is_empty());
+}
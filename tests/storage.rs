@@ -0,0 +1,136 @@
+use meowith_connector::error::{ConnectorError, NodeClientError};
+use meowith_connector::storage::{LocalStorage, MemoryStorage, MeowithStorage};
+use meowith_connector::testing::{MockBucket, MockNode};
+
+fn remote_error<T>(result: Result<T, ConnectorError>) -> NodeClientError {
+    match result {
+        Ok(_) => panic!("expected an error"),
+        Err(err) => err
+            .remote_error()
+            .cloned()
+            .expect("expected a remote error"),
+    }
+}
+
+/// The same scenario has to behave identically on every backend.
+async fn exercise<S: MeowithStorage>(storage: &S) {
+    storage.create_directory("docs").await.unwrap();
+    storage
+        .upload("docs/a.txt", "hello world".into())
+        .await
+        .unwrap();
+    storage.upload("root.txt", "root".into()).await.unwrap();
+
+    let response = storage.download("docs/a.txt").await.unwrap();
+    assert_eq!(response.length, 11);
+    assert_eq!(response.name, "a.txt");
+    assert_eq!(response.bytes().await.unwrap(), "hello world");
+
+    let entity = storage.stat("docs/a.txt").await.unwrap();
+    assert_eq!((entity.name.as_str(), entity.size), ("a.txt", 11));
+    assert!(!entity.is_dir);
+    assert!(storage.stat("docs").await.unwrap().is_dir);
+
+    let names: Vec<_> = storage
+        .list("")
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|entity| entity.name)
+        .collect();
+    assert_eq!(names, ["docs", "root.txt"]);
+
+    let result = storage.create_directory("docs").await;
+    assert_eq!(remote_error(result), NodeClientError::EntityExists);
+    let result = storage.rename("root.txt", "docs").await;
+    assert_eq!(remote_error(result), NodeClientError::EntityExists);
+    let result = storage.upload("missing/a.txt", "a".into()).await;
+    assert_eq!(remote_error(result), NodeClientError::NotFound);
+    let result = storage.delete("docs", false).await;
+    assert_eq!(remote_error(result), NodeClientError::NotEmpty);
+    assert!(remote_error(storage.stat("missing").await).is_not_found());
+    assert!(remote_error(storage.download("docs").await).is_not_found());
+
+    storage.rename("docs", "notes").await.unwrap();
+    storage.rename("notes/a.txt", "notes/b.txt").await.unwrap();
+    let response = storage.download("notes/b.txt").await.unwrap();
+    assert_eq!(response.bytes().await.unwrap(), "hello world");
+    assert!(remote_error(storage.stat("docs/a.txt").await).is_not_found());
+
+    storage.delete("root.txt", false).await.unwrap();
+    storage.delete("notes", true).await.unwrap();
+    assert!(storage.list("").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn memory_storage() {
+    exercise(&MemoryStorage::new()).await;
+}
+
+#[tokio::test]
+async fn memory_storage_quota() {
+    let storage = MemoryStorage::with_quota(10);
+    storage.upload("a.bin", vec![0u8; 8].into()).await.unwrap();
+    let result = storage.upload("b.bin", vec![0u8; 4].into()).await;
+    assert_eq!(remote_error(result), NodeClientError::InsufficientStorage);
+    assert_eq!(storage.space_taken(), 8);
+}
+
+#[tokio::test]
+async fn local_storage() {
+    let dir = tempfile::tempdir().unwrap();
+    exercise(&LocalStorage::new(dir.path())).await;
+}
+
+#[tokio::test]
+async fn local_storage_stays_inside_its_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let storage = LocalStorage::new(dir.path().join("bucket"));
+    std::fs::create_dir(storage.root()).unwrap();
+
+    let result = storage.upload("../escape.txt", "a".into()).await;
+    assert_eq!(remote_error(result), NodeClientError::BadRequest);
+    assert!(!dir.path().join("escape.txt").exists());
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn local_storage_symlinks_stay_inside_its_root() {
+    use std::os::unix::fs::symlink;
+
+    let dir = tempfile::tempdir().unwrap();
+    let outside = dir.path().join("outside");
+    std::fs::create_dir(&outside).unwrap();
+    std::fs::write(outside.join("secret.txt"), "secret").unwrap();
+    let storage = LocalStorage::new(dir.path().join("bucket"));
+    std::fs::create_dir(storage.root()).unwrap();
+    std::fs::create_dir(storage.root().join("docs")).unwrap();
+    symlink(&outside, storage.root().join("escape")).unwrap();
+    symlink(outside.join("new.txt"), storage.root().join("dangling")).unwrap();
+    symlink(storage.root().join("docs"), storage.root().join("inside")).unwrap();
+
+    let result = storage.download("escape/secret.txt").await;
+    assert_eq!(remote_error(result), NodeClientError::BadRequest);
+    let result = storage.upload("escape/new.txt", "a".into()).await;
+    assert_eq!(remote_error(result), NodeClientError::BadRequest);
+    let result = storage.upload("dangling", "a".into()).await;
+    assert_eq!(remote_error(result), NodeClientError::BadRequest);
+    assert_eq!(
+        remote_error(storage.list("escape").await),
+        NodeClientError::BadRequest
+    );
+    assert!(!outside.join("new.txt").exists());
+
+    // Links within the root keep working.
+    storage.upload("inside/a.txt", "a".into()).await.unwrap();
+    let response = storage.download("docs/a.txt").await.unwrap();
+    assert_eq!(response.bytes().await.unwrap(), "a");
+}
+
+#[tokio::test]
+async fn connector_storage() {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    exercise(&node.connector(&bucket)).await;
+}
@@ -0,0 +1,163 @@
+use bytes::Bytes;
+use futures_util::stream;
+use meowith_connector::connector::connector::MeowithConnector;
+use meowith_connector::connector::retry::RetryPolicy;
+use meowith_connector::error::NodeClientError;
+use meowith_connector::testing::{MockBucket, MockNode};
+use std::error::Error;
+use std::io;
+use std::time::Duration;
+
+const THRESHOLD: u64 = 16;
+
+async fn setup() -> (MockNode, MockBucket, MeowithConnector) {
+    let node = MockNode::start().await.unwrap();
+    let bucket = MockBucket::new("files");
+    node.add_bucket(&bucket);
+    let connector = MeowithConnector::builder(
+        "token",
+        bucket.bucket_id,
+        bucket.app_id,
+        node.addr().to_string(),
+    )
+    .durable_threshold(THRESHOLD)
+    .build()
+    .unwrap();
+    (node, bucket, connector)
+}
+
+fn data(len: u64) -> Bytes {
+    (0..len).map(|i| i as u8).collect()
+}
+
+#[tokio::test]
+async fn files_up_to_the_threshold_are_sent_oneshot() {
+    let (node, bucket, connector) = setup().await;
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("a.bin");
+    std::fs::write(&local, data(THRESHOLD)).unwrap();
+
+    connector.upload_file(&local, "a.bin").await.unwrap();
+    assert_eq!(node.file(&bucket, "a.bin").unwrap(), data(THRESHOLD));
+    assert_eq!(node.sessions_started(), 0);
+}
+
+#[tokio::test]
+async fn files_above_the_threshold_are_sent_durably() {
+    let (node, bucket, connector) = setup().await;
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("a.bin");
+    std::fs::write(&local, data(THRESHOLD + 1)).unwrap();
+
+    connector.upload_file(&local, "a.bin").await.unwrap();
+    assert_eq!(node.file(&bucket, "a.bin").unwrap(), data(THRESHOLD + 1));
+    assert_eq!(node.sessions_started(), 1);
+    assert_eq!(node.pending_sessions(), 0);
+}
+
+#[tokio::test]
+async fn readers_of_unknown_length_are_spooled() {
+    let (node, bucket, connector) = setup().await;
+    let small = data(THRESHOLD);
+    connector
+        .upload_reader("small.bin", io::Cursor::new(small.clone()), None)
+        .await
+        .unwrap();
+    assert_eq!(node.file(&bucket, "small.bin").unwrap(), small);
+    assert_eq!(node.sessions_started(), 0);
+
+    let large = data(THRESHOLD * 4 + 3);
+    connector
+        .upload_reader("large.bin", io::Cursor::new(large.clone()), None)
+        .await
+        .unwrap();
+    assert_eq!(node.file(&bucket, "large.bin").unwrap(), large);
+    assert_eq!(node.sessions_started(), 1);
+}
+
+#[tokio::test]
+async fn streams_of_known_length() {
+    let (node, bucket, connector) = setup().await;
+    let chunks = |data: Bytes| {
+        stream::iter(
+            data.chunks(5)
+                .map(|chunk| Ok::<_, io::Error>(Bytes::copy_from_slice(chunk)))
+                .collect::<Vec<_>>(),
+        )
+    };
+
+    connector
+        .upload_stream("small.bin", chunks(data(THRESHOLD)), Some(THRESHOLD))
+        .await
+        .unwrap();
+    assert_eq!(node.sessions_started(), 0);
+
+    connector
+        .upload_stream(
+            "large.bin",
+            chunks(data(THRESHOLD + 1)),
+            Some(THRESHOLD + 1),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        node.file(&bucket, "large.bin").unwrap(),
+        data(THRESHOLD + 1)
+    );
+    assert_eq!(node.sessions_started(), 1);
+
+    // A spooled stream shorter than announced is rejected before anything is sent.
+    let err = connector
+        .upload_stream("short.bin", chunks(data(THRESHOLD)), Some(THRESHOLD * 2))
+        .await
+        .unwrap_err();
+    assert!(err.remote_error().is_none());
+    let source = err.source().unwrap().downcast_ref::<NodeClientError>();
+    assert_eq!(source, Some(&NodeClientError::BadRequest));
+    assert_eq!(node.sessions_started(), 1);
+    assert!(node.file(&bucket, "short.bin").is_none());
+}
+
+#[tokio::test]
+async fn interrupted_uploads_resume_at_the_reported_offset() {
+    let (node, bucket, connector) = setup().await;
+    node.fail_put_after(10);
+    node.fail_put_after(20);
+
+    // Resending anything the node already stored would overflow the announced size.
+    connector
+        .upload_durable("a.bin", &data(THRESHOLD * 4))
+        .await
+        .unwrap();
+    assert_eq!(node.file(&bucket, "a.bin").unwrap(), data(THRESHOLD * 4));
+    assert_eq!(node.sessions_started(), 1);
+    assert_eq!(node.pending_sessions(), 0);
+}
+
+#[tokio::test]
+async fn backoff_longer_than_the_session_validity() {
+    let (node, bucket, _) = setup().await;
+    node.set_session_validity(Duration::from_secs(1));
+    let connector = MeowithConnector::builder(
+        "token",
+        bucket.bucket_id,
+        bucket.app_id,
+        node.addr().to_string(),
+    )
+    .durable_threshold(THRESHOLD)
+    .retry_policy(RetryPolicy {
+        initial_backoff: Duration::from_secs(10),
+        max_backoff: Duration::from_secs(10),
+        jitter: 0.0,
+        ..RetryPolicy::default()
+    })
+    .build()
+    .unwrap();
+    node.fail_put_after(10);
+
+    connector
+        .upload_durable("a.bin", &data(THRESHOLD * 2))
+        .await
+        .unwrap();
+    assert_eq!(node.file(&bucket, "a.bin").unwrap(), data(THRESHOLD * 2));
+}